@@ -0,0 +1,42 @@
+//! Compare the serial and parallel batch validation, run with
+//! `cargo bench --features rayon --bench batch`.
+//!
+//! The parallel version can only be faster with more than one thread,
+//! see `RAYON_NUM_THREADS`.
+
+use std::hint::black_box;
+
+use criterion::{criterion_group, criterion_main, Criterion, Throughput};
+use tommaso_fiscal_code::{validate_batch, validate_batch_parallel};
+
+const CODES: usize = 100_000;
+
+fn batch(c: &mut Criterion) {
+    //spell-checker: disable
+    let codes: Vec<String> = [
+        "GNTMTT99C27H501F",
+        "GNTMTT99C27HRLMD",
+        "GNTMTT99C27H501K",
+        "12345678903",
+    ]
+    .iter()
+    .cycle()
+    .take(CODES)
+    .map(|code| code.to_string())
+    .collect();
+    //spell-checker: enable
+    let serial: Vec<&str> = codes.iter().map(String::as_str).collect();
+    assert_eq!(validate_batch_parallel(&codes), validate_batch(&serial));
+
+    let mut group = c.benchmark_group(format!("batch ({} threads)", rayon::current_num_threads()));
+    group.throughput(Throughput::Elements(CODES as u64));
+    group.sample_size(20);
+    group.bench_function("serial", |b| b.iter(|| validate_batch(black_box(&serial))));
+    group.bench_function("parallel", |b| {
+        b.iter(|| validate_batch_parallel(black_box(&codes)))
+    });
+    group.finish();
+}
+
+criterion_group!(benches, batch);
+criterion_main!(benches);
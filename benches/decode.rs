@@ -0,0 +1,129 @@
+//! Time the decoding of a single code, run with `cargo bench --bench decode`.
+//!
+//! The character classification of [position_errors] is compared with the equivalent
+//! regular expression, and [parse] with a naive decoder working on strings, as a baseline.
+
+use std::collections::HashMap;
+use std::hint::black_box;
+
+use chrono::{Datelike, NaiveDate};
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use regex::Regex;
+use tommaso_fiscal_code::{
+    check_char_even_table, check_char_odd_table, lookup_town, parse, position_errors,
+};
+
+//spell-checker: disable
+const CODES: [&str; 3] = ["GNTMTT99C27H501F", "GNTMTT99C27HRLMD", "GNTMTT99C27H501K"];
+const CODE_PATTERN: &str = r"^[A-Z]{6}[0-9L-NP-V]{2}[A-Z][0-9L-NP-V]{2}[A-Z][0-9L-NP-V]{3}[A-Z]$";
+const OMOCODIA_LETTERS: &str = "LMNPQRSTUV";
+const MONTH_LETTERS: &str = "ABCDEHLMPRST";
+//spell-checker: enable
+
+/// A straightforward decoder, allocating a string for each step:
+/// the birth date and the Belfiore code of the town.
+struct NaiveDecoder {
+    regex: Regex,
+    odd: HashMap<char, u8>,
+    even: HashMap<char, u8>,
+}
+
+impl NaiveDecoder {
+    fn new() -> Self {
+        NaiveDecoder {
+            regex: Regex::new(CODE_PATTERN).unwrap(),
+            odd: check_char_odd_table().collect(),
+            even: check_char_even_table().collect(),
+        }
+    }
+
+    fn decode(&self, code: &str) -> Option<(NaiveDate, String)> {
+        let code = code.trim().to_uppercase();
+        if !self.regex.is_match(&code) {
+            return None;
+        }
+
+        let sum: u32 = code
+            .chars()
+            .take(15)
+            .enumerate()
+            .map(|(i, c)| {
+                u32::from(if i % 2 == 0 {
+                    self.odd[&c]
+                } else {
+                    self.even[&c]
+                })
+            })
+            .sum();
+        let check_character = (b'A' + (sum % 26) as u8) as char;
+        if !code.ends_with(check_character) {
+            return None;
+        }
+
+        let canonical: String = code
+            .chars()
+            .enumerate()
+            .map(|(i, c)| match OMOCODIA_LETTERS.find(c) {
+                Some(digit) if [6, 7, 9, 10, 12, 13, 14].contains(&i) => {
+                    char::from_digit(digit as u32, 10).unwrap()
+                }
+                _ => c,
+            })
+            .collect();
+        let year: i32 = canonical[6..8].parse().ok()?;
+        let month = MONTH_LETTERS.find(&canonical[8..9])? as u32 + 1;
+        let day: u32 = canonical[9..11].parse().ok()?;
+        let day = if day > 40 { day - 40 } else { day };
+        let town = canonical[11..15].to_string();
+        lookup_town(&town)?;
+
+        let current = chrono::Utc::now().year();
+        let mut year = current / 100 * 100 + year;
+        if year > current {
+            year -= 100;
+        }
+        Some((NaiveDate::from_ymd_opt(year, month, day)?, town))
+    }
+}
+
+fn classification(c: &mut Criterion) {
+    let regex = Regex::new(CODE_PATTERN).unwrap();
+    let mut group = c.benchmark_group("classification");
+    for code in CODES {
+        group.bench_with_input(BenchmarkId::new("regex", code), code, |b, code| {
+            b.iter(|| regex.is_match(black_box(code)))
+        });
+        group.bench_with_input(BenchmarkId::new("tables", code), code, |b, code| {
+            b.iter(|| position_errors(black_box(code)))
+        });
+    }
+    group.finish();
+}
+
+fn decode(c: &mut Criterion) {
+    let naive = NaiveDecoder::new();
+    for code in CODES {
+        // both decoders have to agree for the comparison to be fair
+        let expected = parse(code).ok().map(|code| {
+            (
+                code.born_on(),
+                code.representation_canonical()[11..15].to_string(),
+            )
+        });
+        assert_eq!(naive.decode(code), expected, "{}", code);
+    }
+
+    let mut group = c.benchmark_group("decode");
+    for code in CODES {
+        group.bench_with_input(BenchmarkId::new("naive", code), code, |b, code| {
+            b.iter(|| naive.decode(black_box(code)))
+        });
+        group.bench_with_input(BenchmarkId::new("parse", code), code, |b, code| {
+            b.iter(|| parse(black_box(code)))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, classification, decode);
+criterion_main!(benches);
@@ -1,17 +1,36 @@
-use serde::Deserialize;
 use std::collections::HashMap;
 use std::env;
 use std::fs::File;
-use std::io::{BufReader, BufWriter, Write};
-use std::path::Path;
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
 
-#[derive(Debug, Deserialize)]
-#[serde(rename_all = "camelCase")]
-struct Location {
-    country_code: String,
-    country_name: String,
-    city: Option<String>,
-    state: Option<String>,
+#[path = "build/dataset.rs"]
+mod dataset;
+
+use dataset::{read_dataset, Entry, Location};
+
+/// The Rust expression of a location, with its strings escaped by their `Debug` format.
+fn location(value: &Location) -> String {
+    format!(
+        "Location {{
+                    country_code: {:?},
+                    country_name: {:?},
+                    city: {:?},
+                    state: {:?},
+                    region: {:?},
+                    continent: {:?},
+                    valid_from: {:?},
+                    valid_to: {:?},
+                }}",
+        value.country_code,
+        value.country_name,
+        value.city,
+        value.state,
+        value.region,
+        value.continent,
+        value.valid_from,
+        value.valid_to,
+    )
 }
 
 fn main() {
@@ -19,56 +38,116 @@ fn main() {
     let dest_path = Path::new(&out_dir).join("codegen.rs");
     let mut file = BufWriter::new(File::create(dest_path).unwrap());
 
-    let input = File::open("codat.json").unwrap();
-    let reader = BufReader::new(input);
-    let data: HashMap<String, Location> = serde_json::from_reader(reader).unwrap();
+    // the dataset can be overridden, e.g. to point at a newer copy, or split across files
+    // listed like PATH is: the same code can't be in two of them
+    let input_paths: Vec<PathBuf> = match env::var_os("CODAT_JSON_PATH") {
+        Some(paths) => env::split_paths(&paths).collect(),
+        None => vec!["codat.json".into()],
+    };
+    let mut data: HashMap<String, Entry> = HashMap::new();
+    for path in &input_paths {
+        for (key, value) in read_dataset(path) {
+            if data.insert(key.clone(), value).is_some() {
+                panic!(
+                    "The code {} in {} is already in another dataset file, \
+                     use CODAT_JSON_OVERRIDE_PATH to replace it",
+                    key,
+                    path.display()
+                );
+            }
+        }
+    }
+    // the override files are applied last, replacing the codes they contain
+    let override_paths: Vec<PathBuf> = env::var_os("CODAT_JSON_OVERRIDE_PATH")
+        .map(|paths| env::split_paths(&paths).collect())
+        .unwrap_or_default();
+    for path in &override_paths {
+        data.extend(read_dataset(path));
+    }
 
     writeln!(
         &mut file,
         "\
-#[derive(Debug)]
-struct Location<'a> {{
-    country_code: &'a str,
-    country_name: &'a str,
-    city: Option<&'a str>,
-    state: Option<&'a str>,
+/// A place of birth as stored in the town dataset.
+/// See [PlaceOfBirth] for an owned version.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Location<'a> {{
+    pub country_code: &'a str,
+    pub country_name: &'a str,
+    pub city: Option<&'a str>,
+    pub state: Option<&'a str>,
+    /// The region of an Italian town, e.g. `Lombardia`, if known.
+    pub region: Option<&'a str>,
+    /// The continent of a foreign country, e.g. `Europa`, if known.
+    pub continent: Option<&'a str>,
+    /// The first year the code was assigned to this location, if known.
+    pub valid_from: Option<i32>,
+    /// The last year the code was assigned to this location, if known.
+    pub valid_to: Option<i32>,
 }}"
     )
     .unwrap();
 
     let mut map = phf_codegen::Map::new();
-    write!(
-        &mut file,
-        "static BIRTH_TOWNS: phf::Map<&'static str, &'static Location> = {}",
-        {
-            for (key, value) in data {
-                map.entry(
-                    key,
+    let mut history = phf_codegen::Map::new();
+    let mut italian = 0;
+    for (key, value) in &data {
+        match value {
+            Entry::Single(value) => {
+                if value.country_code == "IT" {
+                    italian += 1;
+                }
+                map.entry(key.as_str(), &format!("&{}", location(value)));
+            }
+            Entry::History(values) => {
+                // the current location is the one still valid, or the most recent one
+                let current = values
+                    .iter()
+                    .find(|value| value.valid_to.is_none())
+                    .or(values.last())
+                    .expect("non-empty history");
+                if current.country_code == "IT" {
+                    italian += 1;
+                }
+                map.entry(key.as_str(), &format!("&{}", location(current)));
+                history.entry(
+                    key.as_str(),
                     &format!(
-                        "&Location {{
-                    country_code: \"{}\",
-                    country_name: \"{}\",
-                    city: {},
-                    state: {},
-                }}",
-                        value.country_code,
-                        value.country_name,
-                        match value.city {
-                            Some(v) => format!("Some(\"{}\")", v),
-                            None => "None".to_string(),
-                        },
-                        match value.state {
-                            Some(v) => format!("Some(\"{}\")", v),
-                            None => "None".to_string(),
-                        },
+                        "&[{}]",
+                        values.iter().map(location).collect::<Vec<_>>().join(", ")
                     ),
                 );
             }
-            map.build()
         }
+    }
+    writeln!(
+        &mut file,
+        "static BIRTH_TOWNS: phf::Map<&'static str, &'static Location> = {};",
+        map.build()
+    )
+    .unwrap();
+    writeln!(
+        &mut file,
+        "static BIRTH_TOWN_HISTORY: phf::Map<&'static str, &'static [Location]> = {};",
+        history.build()
+    )
+    .unwrap();
+
+    writeln!(
+        &mut file,
+        "const DATASET_STATS: DatasetStats = DatasetStats {{ towns: {}, italian: {}, foreign: {} }};",
+        data.len(),
+        italian,
+        data.len() - italian
     )
     .unwrap();
-    writeln!(&mut file, ";").unwrap();
+    if data.is_empty() {
+        println!("cargo:warning=The town dataset is empty");
+    }
 
-    println!("cargo:rerun-if-changed=codat.json");
+    for path in input_paths.iter().chain(&override_paths) {
+        println!("cargo:rerun-if-changed={}", path.display());
+    }
+    println!("cargo:rerun-if-env-changed=CODAT_JSON_PATH");
+    println!("cargo:rerun-if-env-changed=CODAT_JSON_OVERRIDE_PATH");
 }
@@ -0,0 +1,37 @@
+//! The schema of the town dataset, shared by the build script and the maintenance examples.
+
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::BufReader;
+use std::path::Path;
+
+#[derive(Debug, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Location {
+    pub country_code: String,
+    pub country_name: String,
+    pub city: Option<String>,
+    pub state: Option<String>,
+    pub region: Option<String>,
+    pub continent: Option<String>,
+    pub valid_from: Option<i32>,
+    pub valid_to: Option<i32>,
+}
+
+/// A code is either assigned to a single location, or reassigned over time.
+#[derive(Debug, PartialEq, Deserialize)]
+#[serde(untagged)]
+pub enum Entry {
+    Single(Location),
+    History(Vec<Location>),
+}
+
+/// Read a dataset file, panicking with a readable message if it's missing or malformed.
+pub fn read_dataset(path: &Path) -> HashMap<String, Entry> {
+    let input = File::open(path)
+        .unwrap_or_else(|err| panic!("Cannot open the town dataset {}: {}", path.display(), err));
+    let reader = BufReader::new(input);
+    serde_json::from_reader(reader)
+        .unwrap_or_else(|err| panic!("Invalid town dataset {}: {}", path.display(), err))
+}
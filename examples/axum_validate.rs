@@ -0,0 +1,64 @@
+//! Validate codes in an axum service, run with
+//! `cargo run --example axum_validate --features serde`.
+//!
+//! `GET /codes/{code}` answers with the decoded info, or with `400 Bad Request`
+//! and the reason why the code was rejected, e.g.
+//! `curl localhost:3000/codes/GNTMTT99C27H501F`.
+
+use axum::{
+    extract::{FromRequestParts, Path},
+    http::{request::Parts, StatusCode},
+    response::{IntoResponse, Response},
+    routing::get,
+    Json, Router,
+};
+use serde_json::json;
+use tommaso_fiscal_code::{parse, FiscalCode, FiscalCodeError, FiscalCodeInfo};
+
+/// A valid code taken from the `code` path parameter.
+struct ValidFiscalCode(FiscalCode);
+
+/// Why the request has no valid code, answered with `400 Bad Request`.
+enum InvalidFiscalCode {
+    Missing,
+    Invalid(FiscalCodeError),
+}
+
+impl IntoResponse for InvalidFiscalCode {
+    fn into_response(self) -> Response {
+        let error = match self {
+            InvalidFiscalCode::Missing => "Missing code".to_string(),
+            InvalidFiscalCode::Invalid(error) => error.to_string(),
+        };
+        (StatusCode::BAD_REQUEST, Json(json!({ "error": error }))).into_response()
+    }
+}
+
+impl<S: Send + Sync> FromRequestParts<S> for ValidFiscalCode {
+    type Rejection = InvalidFiscalCode;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let Path(code) = Path::<String>::from_request_parts(parts, state)
+            .await
+            .map_err(|_| InvalidFiscalCode::Missing)?;
+
+        parse(&code)
+            .map(ValidFiscalCode)
+            .map_err(InvalidFiscalCode::Invalid)
+    }
+}
+
+async fn info(ValidFiscalCode(code): ValidFiscalCode) -> Json<FiscalCodeInfo> {
+    Json(code.info())
+}
+
+#[tokio::main]
+async fn main() {
+    let app = Router::new().route("/codes/{code}", get(info));
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:3000")
+        .await
+        .unwrap();
+    println!("Listening on http://{}", listener.local_addr().unwrap());
+    axum::serve(listener, app).await.unwrap();
+}
@@ -0,0 +1,52 @@
+//! Compare two town datasets before shipping an update, run with
+//! `cargo run --example dataset_diff -- old.json new.json`.
+//!
+//! Each Belfiore code added, removed or changed is printed on its own line,
+//! prefixed by `+`, `-` or `~` respectively, sorted by code.
+
+#[path = "../build/dataset.rs"]
+mod dataset;
+
+use std::collections::BTreeSet;
+use std::env;
+use std::path::Path;
+use std::process::exit;
+
+use dataset::read_dataset;
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let [old, new] = args.as_slice() else {
+        eprintln!("Usage: dataset_diff <old.json> <new.json>");
+        exit(2);
+    };
+    let old = read_dataset(Path::new(old));
+    let new = read_dataset(Path::new(new));
+
+    let codes: BTreeSet<&String> = old.keys().chain(new.keys()).collect();
+    let (mut added, mut removed, mut changed) = (0, 0, 0);
+    for code in codes {
+        match (old.get(code), new.get(code)) {
+            (None, Some(entry)) => {
+                added += 1;
+                println!("+ {}: {:?}", code, entry);
+            }
+            (Some(entry), None) => {
+                removed += 1;
+                println!("- {}: {:?}", code, entry);
+            }
+            (Some(before), Some(after)) if before != after => {
+                changed += 1;
+                println!(
+                    "~ {}: {:?}\n  {}  -> {:?}",
+                    code,
+                    before,
+                    " ".repeat(code.len()),
+                    after
+                );
+            }
+            _ => {}
+        }
+    }
+    eprintln!("{} added, {} removed, {} changed", added, removed, changed);
+}
@@ -0,0 +1,41 @@
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+
+use crate::{canonicalize, FiscalCodeError};
+
+/// Get a stable anonymous identifier for a valid code, as a hex string.
+///
+/// The code is canonicalized first, so all of its omocodia variants map to the same identifier.
+/// The identifier is an HMAC-SHA256 keyed with `salt`: keep it secret, otherwise the
+/// identifiers can be reversed by generating all the plausible codes.
+pub fn anonymize(code: &str, salt: &[u8]) -> Result<String, FiscalCodeError> {
+    let code = canonicalize(code)?;
+
+    let mut mac = Hmac::<Sha256>::new_from_slice(salt).expect("any key length is valid");
+    mac.update(code.as_bytes());
+
+    Ok(mac
+        .finalize()
+        .into_bytes()
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_anonymize() {
+        //spell-checker: disable
+        let id = anonymize("GNTMTT99C27H501F", b"salt").unwrap();
+        assert_eq!(id.len(), 64);
+        assert_eq!(anonymize("gntmtt99c27h501f", b"salt").unwrap(), id);
+        assert_eq!(anonymize("GNTMTT99C27HRLMD", b"salt").unwrap(), id);
+        assert_ne!(anonymize("GNTMTT99C27H501F", b"pepper").unwrap(), id);
+        assert_ne!(anonymize("MKSKRS92L65Z219S", b"salt").unwrap(), id);
+        assert!(anonymize("GNTMTT99C27H501K", b"salt").is_err());
+        //spell-checker: enable
+    }
+}
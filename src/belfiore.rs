@@ -0,0 +1,155 @@
+use std::fmt;
+
+use crate::{lookup_town, FiscalCodeError, Location, PositionKind, BIRTH_TOWNS};
+
+/// A Belfiore code, identifying an Italian town or a foreign country, e.g. `H501` for Roma.
+///
+/// Only the shape is validated (a letter and three digits, uppercased on the way in):
+/// see [BelfioreCode::location] to look it up in the dataset. It dereferences to `&str`,
+/// so it can be passed to [crate::generate] and [lookup_town].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct BelfioreCode([u8; 4]);
+
+impl BelfioreCode {
+    pub fn as_str(&self) -> &str {
+        // only ASCII characters get past validation
+        std::str::from_utf8(&self.0).expect("valid ASCII")
+    }
+
+    /// Foreign countries are identified by codes starting with `Z`.
+    pub fn is_foreign(&self) -> bool {
+        self.0[0] == b'Z'
+    }
+
+    pub fn is_italian(&self) -> bool {
+        !self.is_foreign()
+    }
+
+    /// Look up the code in the dataset compiled into the crate.
+    pub fn location(&self) -> Option<&'static Location<'static>> {
+        lookup_town(self.as_str())
+    }
+
+    /// All the codes in the dataset compiled into the crate, in no particular order.
+    pub fn known() -> impl Iterator<Item = BelfioreCode> {
+        BIRTH_TOWNS
+            .keys()
+            .map(|key| BelfioreCode::try_from(*key).expect("valid dataset key"))
+    }
+}
+
+impl TryFrom<&str> for BelfioreCode {
+    type Error = FiscalCodeError;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        let code = s.trim().to_ascii_uppercase();
+        if !code.is_ascii() {
+            return Err(FiscalCodeError::NonAscii);
+        }
+        if code.len() != 4 {
+            return Err(FiscalCodeError::InvalidLength);
+        }
+        for (position, character) in code.chars().enumerate() {
+            let (valid, expected) = if position == 0 {
+                (character.is_ascii_uppercase(), PositionKind::ExpectedLetter)
+            } else {
+                (character.is_ascii_digit(), PositionKind::ExpectedDigit)
+            };
+            if !valid {
+                return Err(FiscalCodeError::InvalidCharacter { position, expected });
+            }
+        }
+
+        let mut bytes = [0u8; 4];
+        bytes.copy_from_slice(code.as_bytes());
+        Ok(BelfioreCode(bytes))
+    }
+}
+
+impl std::str::FromStr for BelfioreCode {
+    type Err = FiscalCodeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        BelfioreCode::try_from(s)
+    }
+}
+
+impl std::ops::Deref for BelfioreCode {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl AsRef<str> for BelfioreCode {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl fmt::Display for BelfioreCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{generate, Gender};
+    use chrono::NaiveDate;
+
+    #[test]
+    fn test_belfiore_code() {
+        let code = BelfioreCode::try_from(" h501 ").unwrap();
+        assert_eq!(code.to_string(), "H501");
+        assert!(code.is_italian());
+        assert_eq!(code.location().unwrap().city, Some("Roma"));
+
+        let code: BelfioreCode = "Z130".parse().unwrap();
+        assert!(code.is_foreign());
+        assert_eq!(code.location().unwrap().country_code, "SM");
+
+        // the shape is valid even if the code is unknown
+        assert!(BelfioreCode::try_from("A000").unwrap().location().is_none());
+
+        assert_eq!(
+            BelfioreCode::try_from("H50"),
+            Err(FiscalCodeError::InvalidLength)
+        );
+        assert_eq!(
+            BelfioreCode::try_from("1501"),
+            Err(FiscalCodeError::InvalidCharacter {
+                position: 0,
+                expected: PositionKind::ExpectedLetter
+            })
+        );
+        assert_eq!(
+            BelfioreCode::try_from("H5O1"),
+            Err(FiscalCodeError::InvalidCharacter {
+                position: 2,
+                expected: PositionKind::ExpectedDigit
+            })
+        );
+    }
+
+    #[test]
+    fn test_belfiore_code_known() {
+        assert_eq!(BelfioreCode::known().count(), BIRTH_TOWNS.len());
+        assert!(BelfioreCode::known().all(|code| code.location().is_some()));
+    }
+
+    #[test]
+    fn test_belfiore_code_generate() {
+        let born_on = NaiveDate::from_ymd_opt(1999, 3, 27).unwrap();
+        let town = BelfioreCode::try_from("H501").unwrap();
+
+        //spell-checker: disable
+        assert_eq!(
+            generate("Gentile", "Matteo", born_on, Gender::Male, &town).unwrap(),
+            "GNTMTT99C27H501F"
+        );
+        //spell-checker: enable
+    }
+}
@@ -0,0 +1,117 @@
+use crate::{FiscalCodeError, Gender};
+
+/// The day of birth together with the gender, as they are encoded in a code.
+///
+/// The two digits of the code hold the day for males and the day plus 40 for females.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BirthDay {
+    day: u8,
+    gender: Gender,
+}
+
+impl BirthDay {
+    /// A day of the month, in `1..=31`, with the gender.
+    pub fn new(day: u8, gender: Gender) -> Result<Self, FiscalCodeError> {
+        if (1..=31).contains(&day) {
+            Ok(BirthDay { day, gender })
+        } else {
+            Err(FiscalCodeError::InvalidDayOfMonth { day })
+        }
+    }
+
+    /// Decode the two digits of the day field, e.g. `41` is the 1st for a female.
+    pub fn from_encoded(encoded: u8) -> Result<Self, FiscalCodeError> {
+        match encoded {
+            1..=31 => Ok(BirthDay {
+                day: encoded,
+                gender: Gender::Male,
+            }),
+            41..=71 => Ok(BirthDay {
+                day: encoded - 40,
+                gender: Gender::Female,
+            }),
+            _ => Err(FiscalCodeError::InvalidBirthDay { encoded }),
+        }
+    }
+
+    pub fn day(&self) -> u8 {
+        self.day
+    }
+
+    pub fn gender(&self) -> &Gender {
+        &self.gender
+    }
+
+    /// Encode as the two digits of the day field.
+    pub fn to_encoded(&self) -> u8 {
+        match self.gender {
+            Gender::Female => self.day + 40,
+            Gender::Male => self.day,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_encoded() {
+        assert_eq!(
+            BirthDay::from_encoded(41),
+            Ok(BirthDay {
+                day: 1,
+                gender: Gender::Female
+            })
+        );
+        assert_eq!(
+            BirthDay::from_encoded(27),
+            Ok(BirthDay {
+                day: 27,
+                gender: Gender::Male
+            })
+        );
+        assert_eq!(
+            BirthDay::from_encoded(71),
+            Ok(BirthDay {
+                day: 31,
+                gender: Gender::Female
+            })
+        );
+
+        for encoded in [0, 32, 40, 72, 99] {
+            assert_eq!(
+                BirthDay::from_encoded(encoded),
+                Err(FiscalCodeError::InvalidBirthDay { encoded })
+            );
+        }
+    }
+
+    #[test]
+    fn test_new() {
+        assert_eq!(
+            BirthDay::new(1, Gender::Female).map(|day| day.to_encoded()),
+            Ok(41)
+        );
+        assert_eq!(
+            BirthDay::new(31, Gender::Male).map(|day| day.to_encoded()),
+            Ok(31)
+        );
+        for day in [0, 32, 45, 216, 255] {
+            assert_eq!(
+                BirthDay::new(day, Gender::Female),
+                Err(FiscalCodeError::InvalidDayOfMonth { day })
+            );
+        }
+    }
+
+    #[test]
+    fn test_to_encoded() {
+        for encoded in (1..=31).chain(41..=71) {
+            assert_eq!(
+                BirthDay::from_encoded(encoded).unwrap().to_encoded(),
+                encoded
+            );
+        }
+    }
+}
@@ -0,0 +1,26 @@
+use chrono::{NaiveDate, Utc};
+
+/// A source for the current date.
+///
+/// Implement this on targets where the system clock is not available,
+/// or to decode codes as of a fixed reference date.
+pub trait Clock {
+    fn today(&self) -> NaiveDate;
+}
+
+/// The system clock, in UTC. This is what is used by default.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn today(&self) -> NaiveDate {
+        Utc::now().date_naive()
+    }
+}
+
+/// A fixed date works as a clock that is always on that day.
+impl Clock for NaiveDate {
+    fn today(&self) -> NaiveDate {
+        *self
+    }
+}
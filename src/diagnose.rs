@@ -0,0 +1,247 @@
+use std::ops::Range;
+
+use crate::spans::FIELDS;
+use crate::{
+    calculate_check_character, classify, classify_position, field_spans, parse_any,
+    position_errors, BirthDay, FieldKind, FiscalCodeError, FiscalCodeKind, BIRTH_MONTHS,
+    BIRTH_TOWNS,
+};
+
+/// Everything known about why a code is valid or not, see [diagnose].
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Diagnosis {
+    /// The code, trimmed and with its ASCII letters uppercase.
+    pub input: String,
+    pub kind: FiscalCodeKind,
+    /// The fields of a personal code, in order. Empty for the other kinds of code.
+    pub fields: Vec<FieldDiagnosis>,
+    /// Empty for a valid code.
+    pub errors: Vec<DiagnosisError>,
+}
+
+/// A field of a code, see [Diagnosis::fields].
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct FieldDiagnosis {
+    pub kind: FieldKind,
+    /// The characters of the field as found, possibly fewer than expected.
+    pub value: String,
+    /// Whether the field is well-formed and, for the month, day, town and check character,
+    /// whether it decodes. The date as a whole is only checked in [Diagnosis::errors].
+    pub valid: bool,
+}
+
+/// A reason why a code is invalid, see [Diagnosis::errors].
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct DiagnosisError {
+    /// The zero-based position of the wrong character, if the error is about a single one.
+    pub position: Option<usize>,
+    pub message: String,
+}
+
+/// Diagnose a code of any kind, e.g. to log or render why it was rejected.
+///
+/// Unlike [crate::validate_or_error], which stops at the first error, all the characters
+/// that don't fit the layout are reported, each with its position.
+pub fn diagnose(code: &str) -> Diagnosis {
+    let input = code.trim().to_ascii_uppercase();
+    let kind = classify(&input);
+    let numeric = input.chars().all(|c| c.is_ascii_digit());
+    let personal = match kind {
+        FiscalCodeKind::Personal | FiscalCodeKind::PersonalOmocodia => true,
+        FiscalCodeKind::Invalid => !numeric,
+        _ => false,
+    };
+
+    let mut fields = Vec::new();
+    let mut errors = Vec::new();
+    if personal {
+        fields = diagnose_fields(&input);
+        errors = position_errors(&input)
+            .into_iter()
+            .map(|(position, expected)| DiagnosisError {
+                position: Some(position),
+                message: FiscalCodeError::InvalidCharacter { position, expected }.to_string(),
+            })
+            .collect();
+    }
+    if let (FiscalCodeKind::Invalid, Err(error)) = (kind, parse_any(&input)) {
+        // the errors about a single character are already listed
+        let listed = matches!(
+            error,
+            FiscalCodeError::InvalidNameBlock { .. } | FiscalCodeError::InvalidCharacter { .. }
+        );
+        if !listed || errors.is_empty() {
+            errors.push(DiagnosisError {
+                position: None,
+                message: error.to_string(),
+            });
+        }
+    }
+
+    Diagnosis {
+        input,
+        kind,
+        fields,
+        errors,
+    }
+}
+
+/// Check each field of an uppercase personal code on its own.
+fn diagnose_fields(code: &str) -> Vec<FieldDiagnosis> {
+    // the canonical version of each character that fits its position
+    let canonical: Vec<Option<char>> = code
+        .chars()
+        .take(16)
+        .enumerate()
+        .map(|(i, c)| {
+            let byte = if c.is_ascii() { c as u8 } else { 0 };
+            classify_position(i, byte).ok().map(char::from)
+        })
+        .collect();
+    // a field is only decoded if all of its characters are there and fit their positions
+    let field = |positions: Range<usize>| -> Option<String> {
+        canonical
+            .get(positions)
+            .and_then(|characters| characters.iter().copied().collect())
+    };
+
+    field_spans(code)
+        .into_iter()
+        .zip(FIELDS)
+        .map(|(span, (positions, kind))| {
+            let value = field(positions);
+            let valid = match kind {
+                FieldKind::Month => value.is_some_and(|month| {
+                    BIRTH_MONTHS
+                        .values()
+                        .any(|&letter| month == letter.to_string())
+                }),
+                FieldKind::Day => value
+                    .and_then(|day| day.parse().ok())
+                    .is_some_and(|day| BirthDay::from_encoded(day).is_ok()),
+                FieldKind::Town => value.is_some_and(|town| BIRTH_TOWNS.contains_key(&town)),
+                FieldKind::CheckCharacter => field(0..15).is_some_and(|_| {
+                    value.is_some_and(|found| {
+                        found == calculate_check_character(&code[..15]).to_string()
+                    })
+                }),
+                _ => value.is_some(),
+            };
+
+            FieldDiagnosis {
+                kind,
+                value: code[span.start..span.end].to_string(),
+                valid,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_diagnose_valid() {
+        //spell-checker: disable
+        let diagnosis = diagnose(" gntmtt99c27hrlmd ");
+        assert_eq!(diagnosis.input, "GNTMTT99C27HRLMD");
+        assert_eq!(diagnosis.kind, FiscalCodeKind::PersonalOmocodia);
+        assert!(diagnosis.errors.is_empty());
+        assert_eq!(
+            diagnosis
+                .fields
+                .iter()
+                .map(|field| field.value.as_str())
+                .collect::<Vec<_>>(),
+            ["GNT", "MTT", "99", "C", "27", "HRLM", "D"]
+        );
+        assert!(diagnosis.fields.iter().all(|field| field.valid));
+        //spell-checker: enable
+
+        let diagnosis = diagnose("12345678903");
+        assert_eq!(diagnosis.kind, FiscalCodeKind::TemporaryOrVat);
+        assert!(diagnosis.fields.is_empty() && diagnosis.errors.is_empty());
+    }
+
+    #[test]
+    fn test_diagnose_invalid() {
+        //spell-checker: disable
+        let diagnosis = diagnose("GNTMT-99C2AH501F");
+        //spell-checker: enable
+        assert_eq!(diagnosis.kind, FiscalCodeKind::Invalid);
+        assert_eq!(
+            diagnosis
+                .errors
+                .iter()
+                .map(|error| error.position)
+                .collect::<Vec<_>>(),
+            [Some(5), Some(10)]
+        );
+        let invalid: Vec<FieldKind> = diagnosis
+            .fields
+            .iter()
+            .filter(|field| !field.valid)
+            .map(|field| field.kind)
+            .collect();
+        assert_eq!(
+            invalid,
+            [FieldKind::Name, FieldKind::Day, FieldKind::CheckCharacter]
+        );
+
+        //spell-checker: disable
+        let diagnosis = diagnose("GNTMTT99C27A000N");
+        //spell-checker: enable
+        assert_eq!(
+            diagnosis.errors,
+            [DiagnosisError {
+                position: None,
+                message: "Invalid birth town".into()
+            }]
+        );
+        assert!(!diagnosis.fields[5].valid);
+
+        let diagnosis = diagnose("12345678904");
+        assert!(diagnosis.fields.is_empty());
+        assert_eq!(diagnosis.errors.len(), 1);
+
+        let diagnosis = diagnose("GNT");
+        assert_eq!(diagnosis.fields[0].value, "GNT");
+        assert!(diagnosis.fields[0].valid && !diagnosis.fields[1].valid);
+        assert_eq!(diagnosis.errors[0].message, "Invalid length");
+    }
+
+    #[test]
+    fn test_diagnose_non_ascii() {
+        //spell-checker: disable
+        let diagnosis = diagnose("ßNTMTT99C27H501F");
+        //spell-checker: enable
+        // the positions are those of the input, not of its Unicode uppercase `SS`
+        assert_eq!(
+            diagnosis.errors,
+            [
+                DiagnosisError {
+                    position: Some(0),
+                    message: "Invalid character at position 1: expected a letter".into()
+                },
+                DiagnosisError {
+                    position: None,
+                    message: "Invalid non-ASCII character".into()
+                }
+            ]
+        );
+        assert_eq!(diagnosis.fields[0].value, "ßNT");
+        assert!(!diagnosis.fields[0].valid && diagnosis.fields[1].valid);
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn test_diagnose_json() {
+        let value = serde_json::to_value(diagnose("12345678904")).unwrap();
+        assert_eq!(value["kind"], "Invalid");
+        assert_eq!(value["errors"][0]["position"], serde_json::Value::Null);
+    }
+}
@@ -0,0 +1,146 @@
+use chrono::{Month, NaiveDate};
+use std::{error::Error, fmt};
+
+use crate::PositionKind;
+
+/// The reason why a code was rejected.
+///
+/// Every failure has its own variant, so rejections can be told apart
+/// (e.g. with [std::mem::discriminant]) without parsing the message.
+/// More variants may be added in the future, so a `match` needs a wildcard arm.
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub enum FiscalCodeError {
+    /// The code is empty or only whitespace, e.g. a blank `CHAR(16)` column.
+    EmptyInput,
+    InvalidLength,
+    NonAscii,
+    /// The character at the zero-based `position` is lowercase, and
+    /// [crate::ValidateOptions::require_uppercase] is set.
+    LowercaseCharacter {
+        position: usize,
+    },
+    /// A character of the surname or name blocks, at the zero-based `position`, is not a letter,
+    /// e.g. a `0` typed instead of an `O`.
+    InvalidNameBlock {
+        position: usize,
+    },
+    /// The character at the zero-based `position` doesn't fit the layout of a code.
+    InvalidCharacter {
+        position: usize,
+        expected: PositionKind,
+    },
+    InvalidCheckCharacter {
+        found: char,
+        expected: char,
+    },
+    InvalidBirthMonth,
+    /// The day field is neither in `01..=31` (males) nor in `41..=71` (females).
+    InvalidBirthDay {
+        encoded: u8,
+    },
+    /// The day passed to [crate::BirthDay::new] is not in `1..=31`.
+    InvalidDayOfMonth {
+        day: u8,
+    },
+    /// The day doesn't exist in the month, e.g. the 30th of February.
+    InvalidBirthDate {
+        day: u8,
+        month: Month,
+        year: i32,
+    },
+    InvalidBirthTown,
+    /// No foreign Belfiore code is known for the country.
+    UnknownCountry,
+    /// The birth date is outside the range allowed by [crate::ValidateOptions].
+    ImplausibleBirthDate {
+        born_on: NaiveDate,
+    },
+    /// The country of birth is not in [crate::ValidateOptions::allowed_countries].
+    CountryNotAllowed {
+        country_code: String,
+    },
+    InvalidTemporaryCheckCharacter,
+    /// The code is a valid temporary one, which has no birth date nor country of birth for
+    /// the checks of [crate::ValidateOptions].
+    TemporaryCodeNotAllowed,
+    OmocodiaExhausted,
+    /// The code is valid, but it's an omocodia variant and only canonical codes are accepted.
+    OmocodiaNotAllowed,
+}
+
+impl fmt::Display for FiscalCodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FiscalCodeError::EmptyInput => write!(f, "Empty code"),
+            FiscalCodeError::InvalidLength => write!(f, "Invalid length"),
+            FiscalCodeError::NonAscii => write!(f, "Invalid non-ASCII character"),
+            FiscalCodeError::LowercaseCharacter { position } => {
+                write!(
+                    f,
+                    "Invalid lowercase character at position {}",
+                    position + 1
+                )
+            }
+            FiscalCodeError::InvalidNameBlock { position } => write!(
+                f,
+                "Invalid character at position {}: surname and name only contain letters",
+                position + 1
+            ),
+            FiscalCodeError::InvalidCharacter { position, expected } => write!(
+                f,
+                "Invalid character at position {}: expected {}",
+                position + 1,
+                expected
+            ),
+            FiscalCodeError::InvalidCheckCharacter { found, expected } => write!(
+                f,
+                "Invalid check character: found {}, expected {}",
+                found, expected
+            ),
+            FiscalCodeError::InvalidBirthMonth => write!(f, "Invalid birth month"),
+            FiscalCodeError::InvalidBirthDay {
+                encoded: encoded @ 32..=40,
+            } => write!(
+                f,
+                "Invalid birth day: {}, male days go up to 31 and female days start at 41",
+                encoded
+            ),
+            FiscalCodeError::InvalidBirthDay {
+                encoded: encoded @ 72..,
+            } => write!(f, "Invalid birth day: {}, female days go up to 71", encoded),
+            FiscalCodeError::InvalidBirthDay { encoded } => {
+                write!(f, "Invalid birth day: {:02}", encoded)
+            }
+            FiscalCodeError::InvalidDayOfMonth { day } => {
+                write!(f, "Invalid day of the month: {}", day)
+            }
+            FiscalCodeError::InvalidBirthDate {
+                day: 29,
+                month: Month::February,
+                year,
+            } => write!(f, "Day 29 invalid for month February in {}", year),
+            FiscalCodeError::InvalidBirthDate { day, month, .. } => {
+                write!(f, "Day {} invalid for month {}", day, month.name())
+            }
+            FiscalCodeError::InvalidBirthTown => write!(f, "Invalid birth town"),
+            FiscalCodeError::UnknownCountry => write!(f, "Unknown country of birth"),
+            FiscalCodeError::ImplausibleBirthDate { born_on } => {
+                write!(f, "Implausible birth date: {}", born_on)
+            }
+            FiscalCodeError::CountryNotAllowed { country_code } => {
+                write!(f, "Country of birth not allowed: {}", country_code)
+            }
+            FiscalCodeError::InvalidTemporaryCheckCharacter => {
+                write!(f, "Invalid temporary fiscal code")
+            }
+            FiscalCodeError::TemporaryCodeNotAllowed => {
+                write!(f, "Temporary fiscal code not allowed")
+            }
+            FiscalCodeError::OmocodiaExhausted => write!(f, "No omocodia variant available"),
+            FiscalCodeError::OmocodiaNotAllowed => write!(f, "Omocodia code not allowed"),
+        }
+    }
+}
+
+impl Error for FiscalCodeError {}
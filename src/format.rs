@@ -0,0 +1,65 @@
+use crate::FiscalCodeInfo;
+
+/// How to render a [FiscalCodeInfo], see [FiscalCodeInfo::format].
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[non_exhaustive]
+pub enum OutputFormat {
+    /// The same output as the [std::fmt::Display] impl.
+    Plain,
+    #[cfg(feature = "json")]
+    Json,
+    #[cfg(feature = "yaml")]
+    Yaml,
+}
+
+impl FiscalCodeInfo {
+    /// Render the info in the given format.
+    pub fn format(&self, format: OutputFormat) -> String {
+        match format {
+            OutputFormat::Plain => self.to_string(),
+            #[cfg(feature = "json")]
+            OutputFormat::Json => serde_json::to_string(self).expect("serializable info"),
+            #[cfg(feature = "yaml")]
+            OutputFormat::Yaml => serde_norway::to_string(self).expect("serializable info"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::info;
+
+    #[test]
+    fn test_format_plain() {
+        //spell-checker: disable
+        let info = info("RSSMRA85T10H501O").unwrap();
+        //spell-checker: enable
+        assert_eq!(info.format(OutputFormat::Plain), info.to_string());
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn test_format_json() {
+        //spell-checker: disable
+        let info = info("RSSMRA85T10H501O").unwrap();
+        //spell-checker: enable
+        let value: serde_json::Value =
+            serde_json::from_str(&info.format(OutputFormat::Json)).unwrap();
+        assert_eq!(value["born_on"], "1985-12-10");
+        assert_eq!(value["gender"], "M");
+        assert_eq!(value["place_of_birth"]["city"], "Roma");
+        assert_eq!(value["year_is_ambiguous"], true);
+    }
+
+    #[cfg(feature = "yaml")]
+    #[test]
+    fn test_format_yaml() {
+        //spell-checker: disable
+        let info = info("RSSMRA85T10H501O").unwrap();
+        //spell-checker: enable
+        let yaml = info.format(OutputFormat::Yaml);
+        assert!(yaml.contains("born_on: 1985-12-10"));
+        assert!(yaml.contains("city: Roma"));
+    }
+}
@@ -0,0 +1,623 @@
+use chrono::{Datelike, NaiveDate};
+use std::collections::HashSet;
+
+use crate::{
+    calculate_check_character, BirthDay, FiscalCodeError, Gender, BIRTH_MONTHS, BIRTH_TOWNS,
+    DIGIT_REPLACEMENTS, OMOCODIA_INDICES,
+};
+
+/// Generate the Italian Fiscal Code of a person.
+///
+/// `birth_town` is the Belfiore code of the town (or foreign country) of birth,
+/// e.g. `H501` for Roma.
+/// The code generated is the canonical one, see [omocodia_variants] for the alternatives.
+pub fn generate(
+    surname: &str,
+    name: &str,
+    born_on: NaiveDate,
+    gender: Gender,
+    birth_town: &str,
+) -> Result<String, FiscalCodeError> {
+    let birth_town = birth_town.trim().to_ascii_uppercase();
+    if !BIRTH_TOWNS.contains_key(birth_town.as_str()) {
+        return Err(FiscalCodeError::InvalidBirthTown);
+    }
+
+    Ok(encode(
+        &encode_surname(surname),
+        &encode_name(name),
+        born_on,
+        gender,
+        &birth_town,
+    ))
+}
+
+/// Generate the Italian Fiscal Code of a person born abroad, given the country of birth.
+///
+/// `country_iso2` is the ISO 3166-1 alpha-2 code of the country, e.g. `FR`, compared
+/// case-insensitively. If a country has more than one `Z` Belfiore code, the lowest is used.
+/// Italy, which has no `Z` code, and the countries missing from the dataset fail with
+/// [FiscalCodeError::UnknownCountry].
+pub fn generate_foreign(
+    surname: &str,
+    name: &str,
+    born_on: NaiveDate,
+    gender: Gender,
+    country_iso2: &str,
+) -> Result<String, FiscalCodeError> {
+    let birth_town = BIRTH_TOWNS
+        .entries()
+        .filter(|(key, location)| {
+            key.starts_with('Z')
+                && location
+                    .country_code
+                    .eq_ignore_ascii_case(country_iso2.trim())
+        })
+        .map(|(key, _)| *key)
+        .min()
+        .ok_or(FiscalCodeError::UnknownCountry)?;
+
+    generate(surname, name, born_on, gender, birth_town)
+}
+
+/// Generate the Italian Fiscal Code of a person, noting where the encoding lost information.
+///
+/// The surname and name blocks only keep some letters, so the code can't be reversed
+/// into the original data: the notes point out the less obvious losses.
+pub fn generate_with_notes(
+    surname: &str,
+    name: &str,
+    born_on: NaiveDate,
+    gender: Gender,
+    birth_town: &str,
+) -> Result<GeneratedCode, FiscalCodeError> {
+    let code = generate(surname, name, born_on, gender, birth_town)?;
+
+    let mut notes = vec![];
+    let (consonants, vowels) = split_letters(surname);
+    if consonants.len() + vowels.len() < 3 {
+        notes.push(GenerationNote::SurnamePadded);
+    }
+    let (consonants, vowels) = split_letters(name);
+    if consonants.len() + vowels.len() < 3 {
+        notes.push(GenerationNote::NamePadded);
+    }
+    if consonants.len() >= 4 {
+        notes.push(GenerationNote::NameConsonantSkipped);
+    }
+
+    Ok(GeneratedCode { code, notes })
+}
+
+/// A code together with the notes about how it relates to the personal data.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GeneratedCode {
+    pub code: String,
+    pub notes: Vec<GenerationNote>,
+}
+
+/// A part of a code that doesn't map one to one to the personal data.
+///
+/// The same notes are used in both directions:
+/// generating a code (see [generate_with_notes]) and decoding one (see [crate::info_with_notes]).
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[non_exhaustive]
+pub enum GenerationNote {
+    /// The surname has less than three letters, so the block is padded with `X`.
+    SurnamePadded,
+    /// The name has less than three letters, so the block is padded with `X`.
+    NamePadded,
+    /// The name has four or more consonants, so the second one is not encoded.
+    NameConsonantSkipped,
+    /// Only the last two digits of the year are encoded, so the century is a guess.
+    CenturyAssumed,
+    /// The birth year is before the town code was introduced (see [crate::Location::valid_from]),
+    /// which hints at a fabricated code. Only reported when decoding.
+    BornBeforeTown,
+}
+
+/// Generate the Italian Fiscal Code of a person avoiding the ones already assigned.
+///
+/// If the canonical code is taken, the [omocodia_variants] are tried in order
+/// and the first one not in `existing` is returned.
+pub fn generate_resolving_collision(
+    surname: &str,
+    name: &str,
+    born_on: NaiveDate,
+    gender: Gender,
+    birth_town: &str,
+    existing: &HashSet<String>,
+) -> Result<String, FiscalCodeError> {
+    let code = generate(surname, name, born_on, gender, birth_town)?;
+    if !existing.contains(&code) {
+        return Ok(code);
+    }
+
+    omocodia_variants(&code)?
+        .find(|variant| !existing.contains(variant))
+        .ok_or(FiscalCodeError::OmocodiaExhausted)
+}
+
+/// List the codes that can be assigned to people sharing the same canonical code.
+///
+/// Digits are replaced by letters progressively, as the Agenzia delle Entrate does:
+/// the first variant replaces the rightmost digit, the second one the two rightmost digits,
+/// and so on to the left. There are always exactly 7 variants, one per digit position,
+/// each with its own check character. The canonical code itself is not included.
+pub fn omocodia_variants(code: &str) -> Result<impl Iterator<Item = String>, FiscalCodeError> {
+    let canonical = crate::parse(code)?.representation_canonical;
+
+    Ok((1..=OMOCODIA_INDICES.len()).map(move |level| {
+        let altered = &OMOCODIA_INDICES[OMOCODIA_INDICES.len() - level..];
+        let code: String = canonical
+            .char_indices()
+            .take(15)
+            .map(|(i, character)| {
+                if altered.contains(&i) {
+                    let digit = character.to_digit(10).expect("valid digit") as u8;
+                    *DIGIT_REPLACEMENTS
+                        .get(&digit)
+                        .expect("digit replacement found")
+                } else {
+                    character
+                }
+            })
+            .collect();
+        let check_character = calculate_check_character(&code);
+
+        format!("{}{}", code, check_character)
+    }))
+}
+
+/// Regenerate a valid code with a corrected birth date and gender, e.g. when the date on
+/// file was wrong.
+///
+/// The surname, name and town blocks are kept from `code`, so the name of the person
+/// is not needed. The code generated is the canonical one, even if `code` is an omocodia variant.
+pub fn with_birth_date(
+    code: &str,
+    new_date: NaiveDate,
+    gender: Gender,
+) -> Result<String, FiscalCodeError> {
+    let code = crate::parse(code)?;
+    let birth_town = &code.representation_canonical()[11..15];
+
+    Ok(encode(
+        code.surname(),
+        code.name(),
+        new_date,
+        gender,
+        birth_town,
+    ))
+}
+
+/// Score how plausibly a code belongs to someone with the given surname and name,
+/// from `0.0` to `1.0`.
+///
+/// The names are encoded as when generating a code, and the score is the fraction of
+/// the six characters of the surname and name blocks matching the code.
+/// A score of `1.0` is the best possible: the encoding is lossy, so it's not proof.
+pub fn name_match_score(code: &str, surname: &str, name: &str) -> Result<f32, FiscalCodeError> {
+    let code = crate::parse(code)?;
+    let expected = format!("{}{}", encode_surname(surname), encode_name(name));
+    let found = format!("{}{}", code.surname(), code.name());
+
+    let matching = expected
+        .chars()
+        .zip(found.chars())
+        .filter(|(a, b)| a == b)
+        .count();
+    Ok(matching as f32 / 6.0)
+}
+
+/// Count how many more people can share a code through omocodia, i.e. how many
+/// of its seven digit positions still hold a digit.
+///
+/// After canonicalization all of them are digits, so for a canonical code this is always 7,
+/// the number of [omocodia_variants]. Characters past the 15th are ignored.
+pub fn max_omocodia_variants(canonical_code: &str) -> usize {
+    let code = canonical_code.trim().as_bytes();
+    OMOCODIA_INDICES
+        .iter()
+        .filter(|&&i| code.get(i).is_some_and(u8::is_ascii_digit))
+        .count()
+}
+
+/// Put together a canonical code from its blocks, adding the check character.
+fn encode(
+    surname_block: &str,
+    name_block: &str,
+    born_on: NaiveDate,
+    gender: Gender,
+    birth_town: &str,
+) -> String {
+    let month = BIRTH_MONTHS
+        .get(&(born_on.month0() as u8))
+        .expect("month letter found");
+    let day = BirthDay::new(born_on.day() as u8, gender)
+        .expect("day of a date")
+        .to_encoded();
+
+    let code = format!(
+        "{}{}{:02}{}{:02}{}",
+        surname_block,
+        name_block,
+        born_on.year().rem_euclid(100),
+        month,
+        day,
+        birth_town
+    );
+    let check_character = calculate_check_character(&code);
+
+    format!("{}{}", code, check_character)
+}
+
+/// Encode a surname into the first three characters of a code.
+fn encode_surname(surname: &str) -> String {
+    let (consonants, vowels) = split_letters(surname);
+
+    consonants
+        .into_iter()
+        .chain(vowels)
+        .chain(['X'; 3])
+        .take(3)
+        .collect()
+}
+
+/// Encode a name into the characters four to six of a code.
+///
+/// Unlike the surname, when there are four or more consonants the second one is skipped.
+fn encode_name(name: &str) -> String {
+    let (consonants, vowels) = split_letters(name);
+
+    if consonants.len() >= 4 {
+        [consonants[0], consonants[2], consonants[3]]
+            .iter()
+            .collect()
+    } else {
+        consonants
+            .into_iter()
+            .chain(vowels)
+            .chain(['X'; 3])
+            .take(3)
+            .collect()
+    }
+}
+
+/// Split the letters of a name into consonants and vowels, in order.
+/// Accented vowels are reduced to the plain ones, anything else that is not a letter is ignored.
+fn split_letters(s: &str) -> (Vec<char>, Vec<char>) {
+    s.chars()
+        .map(|c| match c.to_lowercase().next().unwrap_or(c) {
+            'à' | 'á' => 'A',
+            'è' | 'é' => 'E',
+            'ì' | 'í' => 'I',
+            'ò' | 'ó' => 'O',
+            'ù' | 'ú' => 'U',
+            c => c.to_ascii_uppercase(),
+        })
+        .filter(char::is_ascii_uppercase)
+        .partition(|c| !matches!(c, 'A' | 'E' | 'I' | 'O' | 'U'))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Days, Months};
+    use proptest::prelude::*;
+    use regex::Regex;
+
+    fn birth_towns() -> impl Strategy<Value = &'static str> {
+        let regex = Regex::new(r"^[A-Z][0-9]{3}$").unwrap();
+        let towns: Vec<&str> = BIRTH_TOWNS
+            .keys()
+            .copied()
+            .filter(|town| regex.is_match(town))
+            .collect();
+        proptest::sample::select(towns)
+    }
+
+    /// The day the codes are decoded on, fixed so that the same dates are checked every day.
+    const TODAY: NaiveDate = NaiveDate::from_ymd_opt(2025, 6, 30).unwrap();
+
+    /// Dates in the hundred years up to [TODAY],
+    /// the only ones whose century is decoded unambiguously.
+    fn birth_dates() -> impl Strategy<Value = NaiveDate> {
+        let first = TODAY - Months::new(100 * 12) + Days::new(1);
+        let days = (TODAY - first).num_days() as u64;
+        (0..=days).prop_map(move |offset| first + Days::new(offset))
+    }
+
+    fn genders() -> impl Strategy<Value = Gender> {
+        prop_oneof![Just(Gender::Female), Just(Gender::Male)]
+    }
+
+    proptest! {
+        #[test]
+        fn test_generate_info_round_trip(
+            surname in "[A-Za-z]{0,12}",
+            name in "[A-Za-z]{0,12}",
+            born_on in birth_dates(),
+            gender in genders(),
+            birth_town in birth_towns(),
+        ) {
+            let code = generate(&surname, &name, born_on, gender.clone(), birth_town).unwrap();
+            let info = crate::info_with_clock(&code, &TODAY).unwrap();
+            let location = BIRTH_TOWNS.get(birth_town).unwrap();
+
+            prop_assert_eq!(
+                crate::parse(&code).unwrap().encoded_year() as i32,
+                born_on.year() % 100
+            );
+
+            prop_assert_eq!(info.born_on, born_on);
+            prop_assert_eq!(info.gender, gender);
+            prop_assert_eq!(info.place_of_birth.country_code, location.country_code);
+            prop_assert_eq!(info.place_of_birth.city.as_deref(), location.city);
+            prop_assert_eq!(info.place_of_birth.state.as_deref(), location.state);
+        }
+
+        #[test]
+        fn test_canonicalize_random_omocodia(
+            born_on in birth_dates(),
+            gender in genders(),
+            birth_town in birth_towns(),
+            altered in proptest::array::uniform7(any::<bool>()),
+        ) {
+            let canonical = generate("Gentile", "Matteo", born_on, gender, birth_town).unwrap();
+            // any subset of the positions, not only the progressive ones
+            let code: String = canonical
+                .char_indices()
+                .take(15)
+                .map(|(i, character)| match OMOCODIA_INDICES.iter().position(|&p| p == i) {
+                    Some(p) if altered[p] => {
+                        let digit = character.to_digit(10).unwrap() as u8;
+                        *DIGIT_REPLACEMENTS.get(&digit).unwrap()
+                    }
+                    _ => character,
+                })
+                .collect();
+            let code = format!("{}{}", code, calculate_check_character(&code));
+
+            prop_assert_eq!(crate::canonicalize(&code).unwrap(), canonical);
+        }
+
+        #[test]
+        fn test_omocodia_variants_round_trip(
+            born_on in birth_dates(),
+            gender in genders(),
+            birth_town in birth_towns(),
+        ) {
+            let code = generate("Gentile", "Matteo", born_on, gender, birth_town).unwrap();
+            let info = crate::info_with_clock(&code, &TODAY).unwrap();
+
+            for variant in omocodia_variants(&code).unwrap() {
+                let variant_info = crate::info_with_clock(&variant, &TODAY).unwrap();
+                prop_assert_eq!(variant_info.born_on, info.born_on);
+                prop_assert_eq!(variant_info.gender, info.gender.clone());
+                prop_assert_eq!(
+                    variant_info.place_of_birth.country_code,
+                    info.place_of_birth.country_code.clone()
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_generate() {
+        let date = |y, m, d| NaiveDate::from_ymd_opt(y, m, d).unwrap();
+
+        //spell-checker: disable
+        assert_eq!(
+            generate("Gentile", "Matteo", date(1999, 3, 27), Gender::Male, "H501").unwrap(),
+            "GNTMTT99C27H501F"
+        );
+        assert_eq!(
+            generate(
+                "Bianchi",
+                "Leonarda",
+                date(1969, 12, 21),
+                Gender::Female,
+                "a783"
+            )
+            .unwrap(),
+            "BNCLRD69T61A783M"
+        );
+        assert_eq!(
+            generate("Fo", "Al", date(2005, 3, 1), Gender::Male, "Z122").unwrap()[..6],
+            *"FOXLAX"
+        );
+        //spell-checker: enable
+        assert_eq!(
+            generate("Rossi", "Mario", date(1980, 1, 1), Gender::Male, "A000"),
+            Err(FiscalCodeError::InvalidBirthTown)
+        );
+    }
+
+    #[test]
+    fn test_generate_with_notes() {
+        let date = NaiveDate::from_ymd_opt(1999, 3, 27).unwrap();
+
+        //spell-checker: disable
+        let generated = generate_with_notes("Gentile", "Matteo", date, Gender::Male, "H501");
+        assert_eq!(
+            generated.unwrap(),
+            GeneratedCode {
+                code: "GNTMTT99C27H501F".into(),
+                notes: vec![]
+            }
+        );
+
+        let generated = generate_with_notes("Fo", "Gianfranco", date, Gender::Male, "H501");
+        //spell-checker: enable
+        assert_eq!(
+            generated.unwrap().notes,
+            vec![
+                GenerationNote::SurnamePadded,
+                GenerationNote::NameConsonantSkipped
+            ]
+        );
+    }
+
+    #[test]
+    fn test_with_birth_date() {
+        let new_date = NaiveDate::from_ymd_opt(1985, 12, 10).unwrap();
+
+        //spell-checker: disable
+        let code = with_birth_date("GNTMTT99C27H501F", new_date, Gender::Female).unwrap();
+        assert_eq!(code, "GNTMTT85T50H501V");
+        assert_eq!(&code[..6], "GNTMTT");
+        assert_eq!(&code[11..15], "H501");
+        let info = crate::info(&code).unwrap();
+        assert_eq!(info.born_on, new_date);
+        assert_eq!(info.gender, Gender::Female);
+
+        // omocodia variants are regenerated in canonical form
+        let code = with_birth_date("GNTMTT99C27HRLMD", new_date, Gender::Male).unwrap();
+        assert_eq!(code, "GNTMTT85T10H501R");
+
+        assert_eq!(
+            with_birth_date("GNTMTT99C27H501K", new_date, Gender::Male),
+            Err(FiscalCodeError::InvalidCheckCharacter {
+                found: 'K',
+                expected: 'F'
+            })
+        );
+        //spell-checker: enable
+    }
+
+    #[test]
+    fn test_generate_foreign() {
+        let born_on = NaiveDate::from_ymd_opt(1999, 3, 27).unwrap();
+
+        //spell-checker: disable
+        let code = generate_foreign("Gentile", "Matteo", born_on, Gender::Male, "fr").unwrap();
+        assert_eq!(&code[11..15], "Z110");
+        assert_eq!(
+            crate::info(&code).unwrap().place_of_birth.country_code,
+            "FR"
+        );
+
+        let code = generate_foreign("Gentile", "Matteo", born_on, Gender::Male, "SM").unwrap();
+        assert_eq!(&code[11..15], "Z130");
+
+        assert_eq!(
+            generate_foreign("Gentile", "Matteo", born_on, Gender::Male, "IT"),
+            Err(FiscalCodeError::UnknownCountry)
+        );
+        assert_eq!(
+            generate_foreign("Gentile", "Matteo", born_on, Gender::Male, "XX"),
+            Err(FiscalCodeError::UnknownCountry)
+        );
+        //spell-checker: enable
+    }
+
+    #[test]
+    fn test_encode() {
+        //spell-checker: disable
+        assert_eq!(encode_surname("De Sanctis"), "DSN");
+        assert_eq!(encode_surname("Rè"), "REX");
+        assert_eq!(encode_surname("Ai"), "AIX");
+        assert_eq!(encode_surname(""), "XXX");
+        assert_eq!(encode_name("Nicolò"), "NCL");
+        assert_eq!(encode_name("Gianfranco"), "GFR");
+        assert_eq!(encode_name("Eva"), "VEA");
+        //spell-checker: enable
+    }
+
+    #[test]
+    fn test_encode_name_skips_second_consonant() {
+        //spell-checker: disable
+        // four or more consonants: 1st, 3rd and 4th
+        assert_eq!(encode_name("Francesco"), "FNC");
+        assert_eq!(encode_surname("Francesco"), "FRN");
+        assert_eq!(encode_name("Alessandro"), "LSN");
+
+        // exactly three consonants: all of them
+        assert_eq!(encode_name("Matteo"), "MTT");
+        assert_eq!(encode_surname("Matteo"), "MTT");
+        //spell-checker: enable
+    }
+
+    #[test]
+    fn test_digit_replacements_unique() {
+        let mut letters: Vec<char> = DIGIT_REPLACEMENTS.values().copied().collect();
+        letters.sort_unstable();
+        letters.dedup();
+        assert_eq!(letters.len(), 10);
+    }
+
+    #[test]
+    fn test_omocodia_variants() {
+        //spell-checker: disable
+        let variants: Vec<String> = omocodia_variants("GNTMTT99C27H50MX").unwrap().collect();
+        assert_eq!(
+            variants,
+            [
+                "GNTMTT99C27H50MX",
+                "GNTMTT99C27H5LMI",
+                "GNTMTT99C27HRLMD",
+                "GNTMTT99C2THRLMA",
+                "GNTMTT99CNTHRLML",
+                "GNTMTT9VCNTHRLMX",
+                "GNTMTTVVCNTHRLMM",
+            ]
+        );
+        assert!(variants.iter().all(|variant| crate::validate(variant)));
+
+        // the order doesn't depend on the variant given
+        assert!(omocodia_variants("GNTMTTVVCNTHRLMM").unwrap().eq(variants));
+        //spell-checker: enable
+    }
+
+    #[test]
+    fn test_name_match_score() {
+        //spell-checker: disable
+        let score = |surname, name| name_match_score("GNTMTT99C27H501F", surname, name).unwrap();
+        assert_eq!(score("Gentile", "Matteo"), 1.0);
+        assert_eq!(score("gentili", "Mattia"), 1.0);
+        assert_eq!(score("Gentile", "Marco"), 4.0 / 6.0);
+        assert_eq!(score("Rossi", "Mario"), 1.0 / 6.0);
+        assert!(name_match_score("GNTMTT99C27H501K", "Gentile", "Matteo").is_err());
+        //spell-checker: enable
+    }
+
+    #[test]
+    fn test_max_omocodia_variants() {
+        //spell-checker: disable
+        assert_eq!(max_omocodia_variants("GNTMTT99C27H501F"), 7);
+        assert_eq!(
+            max_omocodia_variants("GNTMTT99C27H501F"),
+            omocodia_variants("GNTMTT99C27H501F").unwrap().count()
+        );
+        assert_eq!(max_omocodia_variants("GNTMTT99C27HRLMD"), 4);
+        assert_eq!(max_omocodia_variants("GNTMTTVVCNTHRLMM"), 0);
+        //spell-checker: enable
+    }
+
+    #[test]
+    fn test_generate_resolving_collision() {
+        let born_on = NaiveDate::from_ymd_opt(1999, 3, 27).unwrap();
+        let generate = |existing: &HashSet<String>| {
+            generate_resolving_collision(
+                "Gentile",
+                "Matteo",
+                born_on,
+                Gender::Male,
+                "H501",
+                existing,
+            )
+        };
+
+        //spell-checker: disable
+        let mut existing = HashSet::new();
+        assert_eq!(generate(&existing).unwrap(), "GNTMTT99C27H501F");
+
+        existing.insert("GNTMTT99C27H501F".to_string());
+        assert_eq!(generate(&existing).unwrap(), "GNTMTT99C27H50MX");
+
+        existing.extend(omocodia_variants("GNTMTT99C27H501F").unwrap());
+        //spell-checker: enable
+        assert_eq!(generate(&existing), Err(FiscalCodeError::OmocodiaExhausted));
+    }
+}
@@ -0,0 +1,93 @@
+use crate::{validate_or_error, FiscalCodeError};
+
+/// Replace the characters that look like ASCII letters and digits, as found in codes
+/// copied from some PDFs, with their ASCII counterpart.
+///
+/// The mapped code points are:
+/// - the full-width digits and letters, `U+FF10..=U+FF19`, `U+FF21..=U+FF3A` and `U+FF41..=U+FF5A`;
+/// - the Cyrillic `А В Е К М Н О Р С Т Х` (`U+0410`, `U+0412`, `U+0415`, `U+041A`, `U+041C`,
+///   `U+041D`, `U+041E`, `U+0420`, `U+0421`, `U+0422`, `U+0425`) and their lowercase versions,
+///   mapped to `A B E K M H O P C T X`;
+/// - the Greek `Α Β Ε Ζ Η Ι Κ Μ Ν Ο Ρ Τ Υ Χ` (`U+0391`, `U+0392`, `U+0395`, `U+0396`, `U+0397`,
+///   `U+0399`, `U+039A`, `U+039C`, `U+039D`, `U+039F`, `U+03A1`, `U+03A4`, `U+03A5`, `U+03A7`),
+///   mapped to `A B E Z H I K M N O P T Y X`.
+///
+/// Any other character is left untouched.
+pub fn normalize_homoglyphs(code: &str) -> String {
+    code.chars()
+        .map(|character| match character {
+            '\u{FF10}'..='\u{FF19}' | '\u{FF21}'..='\u{FF3A}' | '\u{FF41}'..='\u{FF5A}' => {
+                char::from_u32(character as u32 - 0xFEE0).expect("ASCII character")
+            }
+            'А' | 'а' | 'Α' => 'A',
+            'В' | 'в' | 'Β' => 'B',
+            'Е' | 'е' | 'Ε' => 'E',
+            'К' | 'к' | 'Κ' => 'K',
+            'М' | 'м' | 'Μ' => 'M',
+            'Н' | 'н' | 'Η' => 'H',
+            'О' | 'о' | 'Ο' => 'O',
+            'Р' | 'р' | 'Ρ' => 'P',
+            'С' | 'с' => 'C',
+            'Т' | 'т' | 'Τ' => 'T',
+            'Х' | 'х' | 'Χ' => 'X',
+            'Ζ' => 'Z',
+            'Ι' => 'I',
+            'Ν' => 'N',
+            'Υ' => 'Y',
+            _ => character,
+        })
+        .collect()
+}
+
+/// Same as [validate_or_error], after replacing the lookalike characters listed in
+/// [normalize_homoglyphs]. Temporary codes are supported.
+pub fn validate_lenient(code: &str) -> Result<(), FiscalCodeError> {
+    validate_or_error(&normalize_homoglyphs(code))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_homoglyphs() {
+        //spell-checker: disable
+        // Cyrillic Н, Т and М, Greek Τ
+        assert_eq!(normalize_homoglyphs("GNTМΤT99C27Н501F"), "GNTMTT99C27H501F");
+        assert_eq!(
+            normalize_homoglyphs("ＧＮＴＭＴＴ９９Ｃ２７Ｈ５０１ｆ"),
+            "GNTMTT99C27H501f"
+        );
+        assert_eq!(normalize_homoglyphs("ÈÇ"), "ÈÇ");
+        //spell-checker: enable
+    }
+
+    #[test]
+    fn test_validate_lenient() {
+        //spell-checker: disable
+        assert_eq!(validate_lenient("GNTМΤT99C27Н501F"), Ok(()));
+        assert_eq!(
+            validate_or_error("GNTМΤT99C27Н501F"),
+            Err(FiscalCodeError::NonAscii)
+        );
+        //spell-checker: enable
+    }
+
+    #[test]
+    fn test_validate_lenient_temporary() {
+        // full-width digits are normalized, and rejected without it
+        assert_eq!(validate_lenient("１２３４５６７８９０３"), Ok(()));
+        assert_eq!(
+            validate_lenient("１２３４５６７８９０４"),
+            Err(FiscalCodeError::InvalidTemporaryCheckCharacter)
+        );
+        assert_eq!(
+            validate_or_error("１２３４５６７８９０３"),
+            Err(FiscalCodeError::NonAscii)
+        );
+        assert_eq!(
+            crate::check("１２３４５６７８９０３"),
+            crate::Validity::Invalid(FiscalCodeError::NonAscii)
+        );
+    }
+}
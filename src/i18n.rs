@@ -0,0 +1,147 @@
+use chrono::Month;
+
+use crate::{FiscalCodeError, PositionKind};
+
+/// The language of the messages of [FiscalCodeError::message].
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+#[non_exhaustive]
+pub enum Language {
+    /// The same messages as the [std::fmt::Display] impl.
+    #[default]
+    English,
+    Italian,
+}
+
+impl FiscalCodeError {
+    /// The message of the error in the given language.
+    pub fn message(&self, language: Language) -> String {
+        match language {
+            Language::English => self.to_string(),
+            Language::Italian => self.italian_message(),
+        }
+    }
+
+    fn italian_message(&self) -> String {
+        match self {
+            FiscalCodeError::EmptyInput => "Codice vuoto".into(),
+            FiscalCodeError::InvalidLength => "Lunghezza non valida".into(),
+            FiscalCodeError::NonAscii => "Carattere non ASCII non valido".into(),
+            FiscalCodeError::LowercaseCharacter { position } => {
+                format!(
+                    "Carattere minuscolo non valido in posizione {}",
+                    position + 1
+                )
+            }
+            FiscalCodeError::InvalidNameBlock { position } => format!(
+                "Carattere non valido in posizione {}: cognome e nome contengono solo lettere",
+                position + 1
+            ),
+            FiscalCodeError::InvalidCharacter { position, expected } => format!(
+                "Carattere non valido in posizione {}: atteso {}",
+                position + 1,
+                match expected {
+                    PositionKind::ExpectedLetter => "una lettera",
+                    PositionKind::ExpectedDigit => "una cifra",
+                    PositionKind::ExpectedLetterOrDigit => "una cifra o una lettera di omocodia",
+                }
+            ),
+            FiscalCodeError::InvalidCheckCharacter { found, expected } => format!(
+                "Carattere di controllo non valido: trovato {}, atteso {}",
+                found, expected
+            ),
+            FiscalCodeError::InvalidBirthMonth => "Mese di nascita non valido".into(),
+            FiscalCodeError::InvalidBirthDay {
+                encoded: encoded @ 32..=40,
+            } => format!(
+                "Giorno di nascita non valido: {}, i giorni maschili arrivano al 31 e quelli femminili partono dal 41",
+                encoded
+            ),
+            FiscalCodeError::InvalidBirthDay {
+                encoded: encoded @ 72..,
+            } => format!(
+                "Giorno di nascita non valido: {}, i giorni femminili arrivano al 71",
+                encoded
+            ),
+            FiscalCodeError::InvalidBirthDay { encoded } => {
+                format!("Giorno di nascita non valido: {:02}", encoded)
+            }
+            FiscalCodeError::InvalidDayOfMonth { day } => {
+                format!("Giorno del mese non valido: {}", day)
+            }
+            FiscalCodeError::InvalidBirthDate {
+                day: 29,
+                month: Month::February,
+                year,
+            } => format!("Giorno 29 non valido per il mese di febbraio del {}", year),
+            FiscalCodeError::InvalidBirthDate { day, month, .. } => format!(
+                "Giorno {} non valido per il mese di {}",
+                day,
+                italian_month_name(*month)
+            ),
+            FiscalCodeError::InvalidBirthTown => "Comune di nascita non valido".into(),
+            FiscalCodeError::UnknownCountry => "Stato di nascita sconosciuto".into(),
+            FiscalCodeError::ImplausibleBirthDate { born_on } => {
+                format!("Data di nascita non plausibile: {}", born_on)
+            }
+            FiscalCodeError::CountryNotAllowed { country_code } => {
+                format!("Stato di nascita non ammesso: {}", country_code)
+            }
+            FiscalCodeError::InvalidTemporaryCheckCharacter => {
+                "Codice fiscale provvisorio non valido".into()
+            }
+            FiscalCodeError::TemporaryCodeNotAllowed => {
+                "Codice fiscale provvisorio non ammesso".into()
+            }
+            FiscalCodeError::OmocodiaExhausted => "Nessuna variante di omocodia disponibile".into(),
+            FiscalCodeError::OmocodiaNotAllowed => "Codice con omocodia non consentito".into(),
+        }
+    }
+}
+
+fn italian_month_name(month: Month) -> &'static str {
+    match month {
+        Month::January => "gennaio",
+        Month::February => "febbraio",
+        Month::March => "marzo",
+        Month::April => "aprile",
+        Month::May => "maggio",
+        Month::June => "giugno",
+        Month::July => "luglio",
+        Month::August => "agosto",
+        Month::September => "settembre",
+        Month::October => "ottobre",
+        Month::November => "novembre",
+        Month::December => "dicembre",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::validate_or_error;
+
+    #[test]
+    fn test_message() {
+        assert_eq!(
+            FiscalCodeError::InvalidLength.message(Language::default()),
+            "Invalid length"
+        );
+        assert_eq!(
+            FiscalCodeError::InvalidLength.message(Language::Italian),
+            "Lunghezza non valida"
+        );
+
+        //spell-checker: disable
+        let error = validate_or_error("GNTMTT99C27H501K").unwrap_err();
+        assert_eq!(
+            error.message(Language::Italian),
+            "Carattere di controllo non valido: trovato K, atteso F"
+        );
+        let error = validate_or_error("GNTMTT99B30H501L").unwrap_err();
+        assert_eq!(
+            error.message(Language::Italian),
+            "Giorno 30 non valido per il mese di febbraio"
+        );
+        //spell-checker: enable
+    }
+}
@@ -0,0 +1,77 @@
+use regex::Regex;
+use std::sync::LazyLock;
+
+use crate::{
+    calculate_check_character, parse, patterns, position_errors, FiscalCodeError, PositionKind,
+    OMOCODIA_INDICES,
+};
+
+/// Check if the string provided is a valid legacy 15-character code.
+///
+/// Some codes issued in the 1970s, before the check character was introduced,
+/// have only 15 characters. These are historical, found in old archives, and are
+/// not issued anymore. All the fields are validated as in [crate::validate_or_error];
+/// omocodia letters are rejected, since omocodia handling came later.
+pub fn validate_legacy_15(code: &str) -> Result<(), FiscalCodeError> {
+    static REGEX: LazyLock<Regex> =
+        LazyLock::new(|| Regex::new(patterns::LEGACY_CODE).expect("valid regex"));
+    let code = code.trim().to_ascii_uppercase();
+    if !code.is_ascii() {
+        return Err(FiscalCodeError::NonAscii);
+    }
+    if code.len() != 15 {
+        return Err(FiscalCodeError::InvalidLength);
+    }
+    if !REGEX.is_match(&code) {
+        let error = match position_errors(&code).first() {
+            Some(&(position, expected)) => FiscalCodeError::InvalidCharacter { position, expected },
+            None => FiscalCodeError::InvalidCharacter {
+                position: OMOCODIA_INDICES
+                    .into_iter()
+                    .find(|&i| !code.as_bytes()[i].is_ascii_digit())
+                    .expect("an omocodia letter"),
+                expected: PositionKind::ExpectedDigit,
+            },
+        };
+        return Err(error);
+    }
+
+    // with its check character the code goes through the same decoding as current ones
+    parse(&format!("{}{}", code, calculate_check_character(&code))).map(|_| ())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_legacy_15() {
+        //spell-checker: disable
+        assert_eq!(validate_legacy_15("GNTMTT99C27H501"), Ok(()));
+        assert_eq!(validate_legacy_15(" bnclnr69t61a783 "), Ok(()));
+
+        assert_eq!(
+            validate_legacy_15("GNTMTT99C27H501F"),
+            Err(FiscalCodeError::InvalidLength)
+        );
+        assert_eq!(
+            validate_legacy_15("GNTMTT99C27HRLM"),
+            Err(FiscalCodeError::InvalidCharacter {
+                position: 12,
+                expected: PositionKind::ExpectedDigit
+            })
+        );
+        assert_eq!(
+            validate_legacy_15("GNTMTT99C27H5-1"),
+            Err(FiscalCodeError::InvalidCharacter {
+                position: 13,
+                expected: PositionKind::ExpectedLetterOrDigit
+            })
+        );
+        assert_eq!(
+            validate_legacy_15("GNTMTT99C30A000"),
+            Err(FiscalCodeError::InvalidBirthTown)
+        );
+        //spell-checker: enable
+    }
+}
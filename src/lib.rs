@@ -1,7 +1,64 @@
-use chrono::{Datelike, NaiveDate, Utc};
+use chrono::{Datelike, Month, NaiveDate};
 use phf::phf_ordered_map;
 use regex::Regex;
-use std::{error::Error, fmt};
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::LazyLock;
+
+#[cfg(feature = "anonymize")]
+mod anonymize;
+mod belfiore;
+mod birth_day;
+mod clock;
+mod diagnose;
+mod error;
+mod format;
+mod generate;
+mod homoglyph;
+#[cfg(feature = "i18n")]
+mod i18n;
+mod legacy;
+mod numeric;
+mod options;
+#[cfg(feature = "rayon")]
+mod parallel;
+mod patterns;
+mod repair;
+#[cfg(feature = "serde")]
+mod serde_impls;
+mod spans;
+mod stp;
+#[cfg(feature = "validator")]
+mod validation;
+
+#[cfg(feature = "anonymize")]
+pub use anonymize::anonymize;
+pub use belfiore::BelfioreCode;
+pub use birth_day::BirthDay;
+pub use clock::{Clock, SystemClock};
+pub use diagnose::{diagnose, Diagnosis, DiagnosisError, FieldDiagnosis};
+pub use error::FiscalCodeError;
+pub use format::OutputFormat;
+pub use generate::{
+    generate, generate_foreign, generate_resolving_collision, generate_with_notes,
+    max_omocodia_variants, name_match_score, omocodia_variants, with_birth_date, GeneratedCode,
+    GenerationNote,
+};
+pub use homoglyph::{normalize_homoglyphs, validate_lenient};
+#[cfg(feature = "i18n")]
+pub use i18n::Language;
+pub use legacy::validate_legacy_15;
+pub use numeric::{
+    classify_numeric, temporary_office_code, temporary_sequence, NumericCodeKind, TemporaryInfo,
+};
+pub use options::{validate_with_options, ValidateOptions};
+#[cfg(feature = "rayon")]
+pub use parallel::validate_batch_parallel;
+pub use repair::repair;
+pub use spans::{field_spans, FieldKind, FieldSpan};
+pub use stp::validate_stp_eni;
+#[cfg(feature = "validator")]
+pub use validation::is_fiscal_code;
 
 include!(concat!(env!("OUT_DIR"), "/codegen.rs"));
 
@@ -13,437 +70,2874 @@ pub fn validate(code: &str) -> bool {
 
 /// Check if the string provided is a valid Italian Fiscal Code.
 /// Temporary codes are supported.
-pub fn validate_or_error(code: &str) -> Result<(), Box<dyn Error>> {
-    let code = code.trim().to_uppercase();
-    let regex = Regex::new(r"^\d{11}$").expect("valid regex");
-    if regex.is_match(&code) {
+pub fn validate_or_error(code: &str) -> Result<(), FiscalCodeError> {
+    let code = code.trim().to_ascii_uppercase();
+    if is_temporary_code(&code) {
         // temporary fiscal code
         let (code, check_character) = code.split_at(10);
         return if check_character == calculate_check_character_temporary(code).to_string() {
             Ok(())
         } else {
-            Err("Invalid temporary fiscal code".into())
+            Err(FiscalCodeError::InvalidTemporaryCheckCharacter)
         };
     }
 
-    FiscalCode::try_from(code.as_str()).map(|_| ())
+    parse(&code).map(|_| ())
+}
+
+/// Whether a trimmed code is laid out as a temporary one: 11 ASCII digits.
+fn is_temporary_code(code: &str) -> bool {
+    code.len() == 11 && code.bytes().all(|byte| byte.is_ascii_digit())
+}
+
+/// Same as [validate_or_error], returning the code trimmed and uppercase on success.
+/// Temporary codes are supported.
+pub fn validate_normalized(code: &str) -> Result<String, FiscalCodeError> {
+    let code = code.trim().to_ascii_uppercase();
+    validate_or_error(&code)?;
+    Ok(code)
+}
+
+/// Same as [validate_or_error], rejecting omocodia variants with
+/// [FiscalCodeError::OmocodiaNotAllowed]. Temporary codes are **not** supported.
+pub fn validate_canonical_only(code: &str) -> Result<(), FiscalCodeError> {
+    if parse(code)?.is_omocodia() {
+        Err(FiscalCodeError::OmocodiaNotAllowed)
+    } else {
+        Ok(())
+    }
+}
+
+/// The outcome of [check].
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub enum Validity {
+    ValidPersonal,
+    ValidTemporary,
+    Invalid(FiscalCodeError),
+}
+
+/// Validate a code of either kind, telling which kind it is.
+///
+/// Unlike [verify] nothing is looked up beyond what validation needs,
+/// and unlike [validate_or_error] the kind of a valid code is kept.
+pub fn check(code: &str) -> Validity {
+    let temporary = is_temporary_code(code.trim());
+
+    match validate_or_error(code) {
+        Ok(()) if temporary => Validity::ValidTemporary,
+        Ok(()) => Validity::ValidPersonal,
+        Err(error) => Validity::Invalid(error),
+    }
+}
+
+/// The kind of a code, see [classify].
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[non_exhaustive]
+pub enum FiscalCodeKind {
+    Personal,
+    /// A valid personal code with omocodia alterations.
+    PersonalOmocodia,
+    /// A valid 11-digit code, either a temporary fiscal code or a partita IVA.
+    TemporaryOrVat,
+    /// An STP or ENI health service code, which is not a fiscal code. See [validate_stp_eni].
+    StpEni,
+    Invalid,
+}
+
+/// Tell the kind of a code in a single tag, e.g. to attach it to log records.
+///
+/// See [check] to get the reason why a code is invalid.
+pub fn classify(code: &str) -> FiscalCodeKind {
+    if validate_stp_eni(code) {
+        return FiscalCodeKind::StpEni;
+    }
+
+    match parse_any(code) {
+        Ok(ParsedCode::Personal(code)) if code.is_omocodia() => FiscalCodeKind::PersonalOmocodia,
+        Ok(ParsedCode::Personal(_)) => FiscalCodeKind::Personal,
+        Ok(ParsedCode::Temporary(_)) => FiscalCodeKind::TemporaryOrVat,
+        Err(_) => FiscalCodeKind::Invalid,
+    }
+}
+
+/// Get the canonical version of a valid code, without any omocodia alterations.
+///
+/// All the omocodia variants of a code have the same canonical version.
+pub fn canonicalize(code: &str) -> Result<String, FiscalCodeError> {
+    parse(code).map(|code| code.representation_canonical)
+}
+
+/// Validate many codes at once, see [validate_or_error].
+/// The results are in the same order as the codes.
+pub fn validate_batch(codes: &[&str]) -> Vec<Result<(), FiscalCodeError>> {
+    codes.iter().map(|code| validate_or_error(code)).collect()
+}
+
+/// Validate many codes at once, returning only the invalid ones.
+///
+/// Each failure comes with the index of the code in `codes`, the code itself and the reason,
+/// ordered by index.
+pub fn find_invalid(codes: &[&str]) -> Vec<(usize, String, FiscalCodeError)> {
+    validate_batch(codes)
+        .into_iter()
+        .enumerate()
+        .filter_map(|(i, result)| result.err().map(|error| (i, codes[i].to_string(), error)))
+        .collect()
+}
+
+/// Find the valid codes in a text, e.g. `"Il codice è GNTMTT99C27H501F, grazie"`.
+///
+/// The codes are returned in canonical form (see [canonicalize]), in the order they appear.
+/// Temporary codes are not extracted.
+pub fn extract_codes(text: &str) -> Vec<String> {
+    static REGEX: LazyLock<Regex> =
+        LazyLock::new(|| Regex::new(patterns::EMBEDDED_CODE).expect("valid regex"));
+    REGEX
+        .find_iter(text)
+        .filter_map(|candidate| canonicalize(candidate.as_str()).ok())
+        .collect()
+}
+
+/// Count the valid codes by province of birth, e.g. `RM`.
+///
+/// Invalid codes, temporary codes and codes of people born abroad, which have no province,
+/// are skipped: see [find_invalid] to get the invalid ones.
+pub fn aggregate_by_province(codes: &[&str]) -> HashMap<String, usize> {
+    let mut counts = HashMap::new();
+    for code in codes {
+        if let Some(state) = parse(code).ok().and_then(|code| code.place_of_birth.state) {
+            *counts.entry(state).or_insert(0) += 1;
+        }
+    }
+    counts
+}
+
+/// Parse and validate an Italian Fiscal Code, decoding all of its fields.
+///
+/// This is the main entry point: the other functions are shortcuts built on top of it.
+/// Note that temporary codes are **not** supported.
+pub fn parse(code: &str) -> Result<FiscalCode, FiscalCodeError> {
+    FiscalCode::try_from(code)
+}
+
+/// The outcome of [parse_any].
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub enum ParsedCode {
+    Personal(FiscalCode),
+    Temporary(TemporaryInfo),
+}
+
+/// Same as [parse], also accepting temporary codes.
+///
+/// 11-digit codes are handled as temporary codes (see [classify_numeric]),
+/// anything else as personal codes.
+pub fn parse_any(code: &str) -> Result<ParsedCode, FiscalCodeError> {
+    if is_temporary_code(code.trim()) {
+        let kind = classify_numeric(code)?;
+        return Ok(ParsedCode::Temporary(TemporaryInfo {
+            code: code.trim().to_string(),
+            kind,
+        }));
+    }
+
+    parse(code).map(ParsedCode::Personal)
 }
 
 /// This function expects a valid Italian Fiscal Code as input.
 ///
 /// You can use [validate] to check if the code is correct before calling this.
 /// Note that temporary codes are **not** supported.
-pub fn info(code: &str) -> Result<FiscalCodeInfo, Box<dyn Error>> {
-    let code = FiscalCode::try_from(code)?;
+pub fn info(code: &str) -> Result<FiscalCodeInfo, FiscalCodeError> {
+    let code = parse(code)?;
 
-    Ok(FiscalCodeInfo {
-        born_on: code.born_on,
-        gender: code.gender,
-        place_of_birth: code.place_of_birth,
-    })
+    Ok(code.info())
 }
 
+/// The outcome of [verify].
 #[derive(Debug, Clone)]
-pub struct FiscalCodeInfo {
-    pub born_on: NaiveDate,
-    pub gender: Gender,
-    pub place_of_birth: PlaceOfBirth,
+#[non_exhaustive]
+pub enum VerifyResult {
+    Valid(FiscalCodeInfo),
+    ValidTemporary(TemporaryInfo),
+    Invalid(FiscalCodeError),
 }
 
-#[derive(Debug, Clone, PartialEq)]
-pub enum Gender {
-    Female,
-    Male,
+/// Validate a code of either kind, getting everything known about it in a single call.
+///
+/// 11-digit codes are handled as temporary codes (see [classify_numeric]),
+/// anything else as personal codes (see [info]).
+pub fn verify(code: &str) -> VerifyResult {
+    if is_temporary_code(code.trim()) {
+        return match classify_numeric(code) {
+            Ok(kind) => VerifyResult::ValidTemporary(TemporaryInfo {
+                code: code.trim().to_string(),
+                kind,
+            }),
+            Err(error) => VerifyResult::Invalid(error),
+        };
+    }
+
+    match info(code) {
+        Ok(info) => VerifyResult::Valid(info),
+        Err(error) => VerifyResult::Invalid(error),
+    }
 }
 
-impl fmt::Display for Gender {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(
-            f,
-            "{}",
-            match &self {
-                Gender::Female => "F",
-                Gender::Male => "M",
-            }
-        )
+/// Same as [info], decoding the code at face value without reverting omocodia.
+///
+/// A letter where a digit is expected is reported as [FiscalCodeError::InvalidCharacter],
+/// telling apart corrupt codes from the omocodia ones that [info] accepts.
+pub fn info_raw(code: &str) -> Result<FiscalCodeInfo, FiscalCodeError> {
+    let normalized = code.trim().to_ascii_uppercase();
+    if normalized.is_ascii() && normalized.len() == 16 {
+        if let Some(&position) = OMOCODIA_INDICES
+            .iter()
+            .find(|&&i| !normalized.as_bytes()[i].is_ascii_digit())
+        {
+            return Err(FiscalCodeError::InvalidCharacter {
+                position,
+                expected: PositionKind::ExpectedDigit,
+            });
+        }
     }
+
+    // without omocodia letters there's nothing to revert
+    info(&normalized)
 }
 
-#[derive(Debug, Clone)]
-pub struct PlaceOfBirth {
-    pub country_code: String,
-    pub country_name: String,
-    pub city: Option<String>,
-    pub state: Option<String>,
+/// Same as [info], using `clock` to know the current date.
+///
+/// The current date is needed to solve the century of the birth year,
+/// the most recent one that doesn't put the birth in the future is picked.
+pub fn info_with_clock(code: &str, clock: &dyn Clock) -> Result<FiscalCodeInfo, FiscalCodeError> {
+    let code = FiscalCode::decode(code, clock, &HashMap::new(), &[])?;
+
+    Ok(code.info())
 }
 
-impl fmt::Display for PlaceOfBirth {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(
-            f,
-            "Country: {} ({})\n\tCity: {} ({})",
-            self.country_name,
-            self.country_code,
-            self.city.clone().unwrap_or("N/A".into()),
-            self.state.clone().unwrap_or("N/A".into())
-        )
+/// Same as [info], looking up the place of birth in `extra_towns` before the dataset.
+///
+/// This allows decoding codes of towns missing from the compiled dataset, e.g. new ones.
+/// On key collision `extra_towns` takes precedence.
+pub fn info_with_extra_towns(
+    code: &str,
+    extra_towns: &HashMap<String, Location>,
+) -> Result<FiscalCodeInfo, FiscalCodeError> {
+    let code = FiscalCode::decode(code, &SystemClock, extra_towns, &[])?;
+
+    Ok(code.info())
+}
+
+/// Same as [info], accepting codes whose town is one of the `placeholder_towns`,
+/// e.g. `XXXX`, used by some provisional records instead of a real Belfiore code.
+///
+/// The placeholders are compared with the uppercase code, and must be made of 4 letters or
+/// digits. The place of birth of such a code is unknown, see [PlaceOfBirth::is_placeholder].
+pub fn info_with_placeholder_towns(
+    code: &str,
+    placeholder_towns: &[&str],
+) -> Result<FiscalCodeInfo, FiscalCodeError> {
+    let code = FiscalCode::decode(code, &SystemClock, &HashMap::new(), placeholder_towns)?;
+
+    Ok(code.info())
+}
+
+/// Same as [info], together with the notes about what the code can't tell for sure.
+///
+/// The century is reported as assumed when the year is ambiguous
+/// (see [FiscalCodeInfo::year_is_ambiguous]), while padding is only reported when it's
+/// unambiguous, i.e. when the `X` follow a vowel. When the dataset knows when the town
+/// code was introduced, a birth before that is reported too, as a warning.
+pub fn info_with_notes(
+    code: &str,
+) -> Result<(FiscalCodeInfo, Vec<GenerationNote>), FiscalCodeError> {
+    let code = parse(code)?;
+
+    let padded = |block: &str| {
+        let trimmed = block.trim_end_matches('X');
+        trimmed.len() < block.len() && trimmed.ends_with(['A', 'E', 'I', 'O', 'U'])
+    };
+    let mut notes = vec![];
+    if padded(&code.surname) {
+        notes.push(GenerationNote::SurnamePadded);
     }
+    if padded(&code.name) {
+        notes.push(GenerationNote::NamePadded);
+    }
+    if code.year_is_ambiguous {
+        notes.push(GenerationNote::CenturyAssumed);
+    }
+    let locations = all_locations_for(&code.representation_canonical[11..15]);
+    if born_before_town(&locations, code.born_on.year()) {
+        notes.push(GenerationNote::BornBeforeTown);
+    }
+
+    Ok((code.info(), notes))
 }
 
-fn calculate_check_character(code: &str) -> char {
-    let mut sum = 0;
-    for (i, character) in code[..code.len() - 1].char_indices() {
-        if (i + 1) % 2 == 0 {
-            sum += CHECK_CHARACTER_EVEN_REPLACEMENTS
-                .get(&character)
-                .expect("character replacement found");
-        } else {
-            sum += CHECK_CHARACTER_ODD_REPLACEMENTS
-                .get(&character)
-                .expect("character replacement found");
+/// Counts of the town dataset compiled into the crate, see [dataset_stats].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DatasetStats {
+    /// The number of Belfiore codes.
+    pub towns: usize,
+    /// The codes of Italian towns.
+    pub italian: usize,
+    /// The codes of foreign countries.
+    pub foreign: usize,
+}
+
+/// Get the counts of the town dataset the crate was compiled with,
+/// e.g. to check a dataset set with `CODAT_JSON_PATH` was picked up entirely.
+pub fn dataset_stats() -> DatasetStats {
+    DATASET_STATS
+}
+
+/// Look up a town (or foreign country) by its Belfiore code, e.g. `H501` for Roma.
+///
+/// This borrows from the dataset compiled into the crate, avoiding the allocations
+/// of a [PlaceOfBirth]. The code must be uppercase.
+pub fn lookup_town(belfiore: &str) -> Option<&'static Location<'static>> {
+    BIRTH_TOWNS.get(belfiore).copied()
+}
+
+/// List the distinct countries in the dataset as `(country_code, country_name)`,
+/// sorted by name. Italy is included.
+pub fn countries() -> Vec<(&'static str, &'static str)> {
+    let mut countries: Vec<(&'static str, &'static str)> = BIRTH_TOWNS
+        .values()
+        .map(|location| (location.country_code, location.country_name))
+        .collect();
+    countries.sort_unstable_by_key(|&(code, name)| (name, code));
+    countries.dedup();
+    countries
+}
+
+/// List the Belfiore codes of the towns in an Italian region, e.g. `Lombardia`, sorted.
+///
+/// The region is compared case-insensitively with its name in the dataset,
+/// and an unknown region gives an empty list.
+pub fn belfiore_codes_in_region(region: &str) -> Vec<&'static str> {
+    let region = region.trim();
+    let mut codes: Vec<&'static str> = BIRTH_TOWNS
+        .entries()
+        .filter(|(_, location)| {
+            location
+                .region
+                .is_some_and(|name| name.eq_ignore_ascii_case(region))
+        })
+        .map(|(&code, _)| code)
+        .collect();
+    codes.sort_unstable();
+    codes
+}
+
+/// The maximum number of codes returned by [suggest_belfiore].
+pub const MAX_BELFIORE_SUGGESTIONS: usize = 10;
+
+/// Suggest the known Belfiore codes at an edit distance of 1 from `belfiore`, with a single
+/// character replaced, added or removed, e.g. to correct a typo in the town of a code.
+///
+/// The input is uppercased, and is not suggested even if it's known. At most
+/// [MAX_BELFIORE_SUGGESTIONS] codes are returned, sorted.
+pub fn suggest_belfiore(belfiore: &str) -> Vec<&'static str> {
+    let belfiore: Vec<char> = belfiore.trim().to_ascii_uppercase().chars().collect();
+    let mut suggestions: Vec<&'static str> = BIRTH_TOWNS
+        .keys()
+        .copied()
+        .filter(|known| {
+            let known: Vec<char> = known.chars().collect();
+            one_edit_apart(&known, &belfiore)
+        })
+        .collect();
+    suggestions.sort_unstable();
+    suggestions.truncate(MAX_BELFIORE_SUGGESTIONS);
+    suggestions
+}
+
+/// Whether the Levenshtein distance between `a` and `b` is exactly 1.
+fn one_edit_apart(a: &[char], b: &[char]) -> bool {
+    let (shorter, longer) = if a.len() <= b.len() { (a, b) } else { (b, a) };
+    match longer.len() - shorter.len() {
+        0 => a.iter().zip(b).filter(|(a, b)| a != b).count() == 1,
+        1 => {
+            // after the common prefix, skipping a character of the longer one leaves the same
+            let prefix = shorter
+                .iter()
+                .zip(longer)
+                .take_while(|(a, b)| a == b)
+                .count();
+            shorter[prefix..] == longer[prefix + 1..]
         }
+        _ => false,
     }
+}
 
-    CHECK_CHARACTER_REMINDER
-        .get(&(sum % 26))
-        .copied()
-        .expect("value replacement found")
+/// Get every location a Belfiore code has been assigned to over time.
+///
+/// A few codes were reassigned to different towns: when the dataset carries their history,
+/// [Location::valid_from] and [Location::valid_to] tell which one applies to a given year.
+/// Otherwise this is the same as [lookup_town], and the result is empty for unknown codes.
+pub fn all_locations_for(belfiore: &str) -> Vec<&'static Location<'static>> {
+    match BIRTH_TOWN_HISTORY.get(belfiore) {
+        Some(locations) => locations.iter().collect(),
+        None => lookup_town(belfiore).into_iter().collect(),
+    }
 }
 
-fn calculate_check_character_temporary(code: &str) -> char {
-    let digits: Vec<u8> = code
-        .chars()
-        .map(|c| c.to_digit(10).expect("valid digit") as u8)
-        .collect();
+/// Check if the birth date encoded in the code is the one expected.
+///
+/// The code only stores the last two digits of the year, so the comparison is
+/// century-tolerant: both the 19xx and the 20xx interpretations are accepted,
+/// e.g. a code for 1990 matches both 1990-01-01 and 2090-01-01.
+pub fn born_on_matches(code: &str, expected: NaiveDate) -> Result<bool, FiscalCodeError> {
+    let code = parse(code)?;
+    let year = code.born_on.year() % 100;
 
-    let odd_sum: u8 = digits.iter().step_by(2).sum();
-    let even_sum: u8 = digits
+    Ok([1900, 2000]
         .iter()
-        .skip(1)
-        .step_by(2)
-        .map(|&digit| {
-            let doubled = digit * 2;
-            if doubled >= 10 {
-                doubled - 9
+        .filter_map(|century| code.born_on.with_year(century + year))
+        .any(|candidate| candidate == expected))
+}
+
+/// Get a key of the code that ignores the gender, e.g. to find records that only differ
+/// because of a gender typo.
+///
+/// The key is the canonical code without the check character, and with the day of birth
+/// stored without the offset for women: e.g. `GNTMTT99C27H501` for both `GNTMTT99C27H501F`
+/// and `GNTMTT99C67H501J`. Use [FiscalCode::gender] to get the gender separately.
+pub fn birth_key(code: &str) -> Result<String, FiscalCodeError> {
+    let code = parse(code)?;
+    let canonical = &code.representation_canonical;
+
+    Ok(format!(
+        "{}{:02}{}",
+        &canonical[..9],
+        code.born_on.day(),
+        &canonical[11..15]
+    ))
+}
+
+/// Check if the country of birth encoded in the code is the one expected.
+///
+/// `iso2` is the ISO 3166-1 alpha-2 code of the country, e.g. `IT` for people born
+/// in Italy, compared case-insensitively.
+pub fn birth_country_matches(code: &str, iso2: &str) -> Result<bool, FiscalCodeError> {
+    let code = parse(code)?;
+
+    Ok(code
+        .place_of_birth
+        .country_code
+        .eq_ignore_ascii_case(iso2.trim()))
+}
+
+/// Check if the owner of the code is at least `years` old on `as_of`, e.g. for an age gate.
+///
+/// The century is solved as in [info_with_clock], using `as_of` as the current date.
+/// A code of someone born in the 1900s can only be read as the 2000s when that would still
+/// be before `as_of`, i.e. for people aged 100 or more, so the check is reliable for adults.
+/// People born on the 29th of February come of age on the 1st of March in non-leap years.
+pub fn is_at_least(code: &str, years: u32, as_of: NaiveDate) -> Result<bool, FiscalCodeError> {
+    let born_on = FiscalCode::decode(code, &as_of, &HashMap::new(), &[])?.born_on;
+
+    let birthday_passed = (as_of.month(), as_of.day()) >= (born_on.month(), born_on.day());
+    let age = as_of.year() - born_on.year() - if birthday_passed { 0 } else { 1 };
+    Ok(age >= years as i32)
+}
+
+/// Check if the town of birth encoded in the code is the one expected.
+///
+/// `expected_belfiore` is the Belfiore code of the town (or foreign country), e.g. `H501`.
+/// Omocodia alterations of the code are reverted before comparing.
+pub fn birthplace_matches(code: &str, expected_belfiore: &str) -> Result<bool, FiscalCodeError> {
+    let code = parse(code)?;
+
+    Ok(code.representation_canonical[11..15] == expected_belfiore.trim().to_ascii_uppercase())
+}
+
+/// Compare a code with the expected one, e.g. a stored one, in constant time.
+///
+/// Case and surrounding whitespace are ignored. The time taken depends only on the lengths,
+/// not on where the codes differ, so it doesn't leak how much of a guess is right.
+/// The codes are not validated, and omocodia variants don't match the canonical code.
+pub fn verify_against(code: &str, expected: &str) -> bool {
+    let code = code.trim().as_bytes();
+    let expected = expected.trim().as_bytes();
+    if code.len() != expected.len() {
+        return false;
+    }
+
+    let difference = code.iter().zip(expected).fold(0u8, |difference, (a, b)| {
+        difference | (a.to_ascii_uppercase() ^ b.to_ascii_uppercase())
+    });
+    std::hint::black_box(difference) == 0
+}
+
+/// Group the code as printed on official forms, e.g. `GNT MTT 99C27 H501 F`.
+///
+/// The code is validated first, omocodia letters are kept as they are.
+pub fn format_grouped(code: &str) -> Result<String, FiscalCodeError> {
+    let code = parse(code)?;
+    let code = code.representation();
+    Ok(format!(
+        "{} {} {} {} {}",
+        &code[..3],
+        &code[3..6],
+        &code[6..11],
+        &code[11..15],
+        &code[15..]
+    ))
+}
+
+/// A reserved value to store before the real code is known, instead of inventing one.
+///
+/// It has the layout of a code and a correct check character, but [validate] always
+/// rejects it because neither its day `00` nor its month `X` exist.
+/// See [is_placeholder] to recognize it.
+pub const PLACEHOLDER: &str = "XXXXXX00X00X000O";
+
+/// Whether the code is the [PLACEHOLDER], ignoring case and surrounding whitespace.
+pub fn is_placeholder(code: &str) -> bool {
+    code.trim().eq_ignore_ascii_case(PLACEHOLDER)
+}
+
+/// The positions left visible by [mask]: the surname and name blocks and the check character.
+pub const UNMASKED_POSITIONS: [usize; 7] = [0, 1, 2, 3, 4, 5, 15];
+
+/// Hide the personal data of a code for logging, e.g. `GNTMTT*********F`.
+///
+/// The birth date, gender and town are replaced by `*`, see [UNMASKED_POSITIONS].
+/// The code is not validated, so invalid input is masked the same way.
+pub fn mask(code: &str) -> String {
+    mask_with(code, &UNMASKED_POSITIONS)
+}
+
+/// Same as [mask], choosing which positions are left visible.
+pub fn mask_with(code: &str, unmasked: &[usize]) -> String {
+    code.trim()
+        .chars()
+        .enumerate()
+        .map(|(i, character)| {
+            if unmasked.contains(&i) {
+                // uppercasing the whole code first could change its length, e.g. `ß` to `SS`
+                character.to_ascii_uppercase()
             } else {
-                doubled
+                '*'
             }
         })
-        .sum();
+        .collect()
+}
 
-    let total = odd_sum + even_sum;
-    let units = total % 10;
-    ((10 - units) % 10 + 48) as char
+/// Check if the surname and name blocks look like they were encoded from real names.
+///
+/// This is an advisory heuristic, meant to route suspicious codes to a manual review,
+/// and it doesn't validate the rest of the code.
+/// A block is plausible when it has consonants, then vowels, then `X` padding, in this order,
+/// and it's not `XXX`, which requires an exceptionally short name.
+pub fn plausible_name_block(code: &str) -> bool {
+    static REGEX: LazyLock<Regex> =
+        LazyLock::new(|| Regex::new(patterns::NAME_BLOCK).expect("valid regex"));
+    let code = code.trim().to_ascii_uppercase();
+    if !code.is_ascii() {
+        return false;
+    }
+
+    match code.get(..6) {
+        Some(block) => [&block[..3], &block[3..]]
+            .iter()
+            .all(|part| *part != "XXX" && REGEX.is_match(part)),
+        None => false,
+    }
 }
 
-#[allow(dead_code)]
-#[derive(Debug, Clone)]
-struct FiscalCode {
-    /// The string representing this code
-    representation: String,
-    /// The string representing this code without any omocodia alterations
-    representation_canonical: String,
-    surname: String,
-    name: String,
-    born_on: NaiveDate,
-    gender: Gender,
-    place_of_birth: PlaceOfBirth,
+/// How much a code looks like it was issued to a real person, see [confidence].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[non_exhaustive]
+pub enum Confidence {
+    /// The code is invalid, e.g. its check character is wrong or its date doesn't exist.
+    Low,
+    /// The check character is correct, but either the town is unknown (the dataset may
+    /// be outdated) or the name blocks are implausible, see [plausible_name_block].
+    Medium,
+    /// The code is valid, and its name blocks are plausible.
+    High,
 }
 
-impl TryFrom<&str> for FiscalCode {
-    type Error = Box<dyn Error>;
+/// Score a code for fraud triage, combining validation with the advisory checks.
+///
+/// A random string has about one chance in 26 of having the right check character,
+/// so a code that only passes the checksum gets a lower score. This doesn't change the
+/// outcome of [validate]. Temporary codes are **not** supported, and are scored `Low`.
+pub fn confidence(code: &str) -> Confidence {
+    match parse(code) {
+        Ok(_) if plausible_name_block(code) => Confidence::High,
+        Ok(_) | Err(FiscalCodeError::InvalidBirthTown) => Confidence::Medium,
+        Err(_) => Confidence::Low,
+    }
+}
 
-    fn try_from(s: &str) -> Result<Self, Self::Error> {
-        let code = s.trim().to_uppercase();
-        if code.len() != 16 {
-            return Err("Invalid length".into());
-        }
-        let regex = Regex::new(r"([A-Z]{3})([A-Z]{3})(\d{2})([A-Z])(\d{2})([A-Z]\d{3})([A-Z])")
-            .expect("valid regex");
-
-        let check_character_calculated = calculate_check_character(&code.to_string());
-
-        // get the original code that may be modified in case of omocodia
-        let code_canonical: String = {
-            let indices = [6usize, 7, 9, 10, 12, 13, 14];
-            code.char_indices()
-                .map(|(i, character)| {
-                    if indices.contains(&i) {
-                        DIGIT_REPLACEMENTS
-                            .into_iter()
-                            .find(|(_, &value)| value == character)
-                            // convert to the correct ASCII char
-                            .map_or(character, |(&key, _)| (key + 48) as char)
-                    } else {
-                        character
-                    }
-                })
-                .collect()
+/// List the positions of a valid code that were substituted with a letter because of omocodia.
+///
+/// The returned positions are sorted and empty for a code without alterations.
+pub fn altered_positions(code: &str) -> Result<Vec<usize>, FiscalCodeError> {
+    let code = parse(code)?;
+
+    Ok(code
+        .representation
+        .char_indices()
+        .zip(code.representation_canonical.chars())
+        .filter(|&((i, actual), canonical)| OMOCODIA_INDICES.contains(&i) && actual != canonical)
+        .map(|((i, _), _)| i)
+        .collect())
+}
+
+/// Show how the check character of a code is computed, given its first 15 characters.
+///
+/// Returns the value of each character (odd and even positions use different tables),
+/// the reminder of their sum divided by 26 and the resulting check character.
+pub fn check_character_breakdown(first_15: &str) -> Result<(Vec<u8>, u8, char), FiscalCodeError> {
+    let code = first_15.trim().to_ascii_uppercase();
+    if !code.is_ascii() {
+        return Err(FiscalCodeError::NonAscii);
+    }
+    if code.len() != 15 {
+        return Err(FiscalCodeError::InvalidLength);
+    }
+    if let Some(&(position, expected)) = position_errors(&code).first() {
+        return Err(FiscalCodeError::InvalidCharacter { position, expected });
+    }
+
+    let contributions = check_character_contributions(&code);
+    let sum: u32 = contributions.iter().map(|&value| u32::from(value)).sum();
+    let reminder = (sum % 26) as u8;
+    let check_character = CHECK_CHARACTER_REMINDER
+        .get(&reminder)
+        .copied()
+        .expect("value replacement found");
+
+    Ok((contributions, reminder, check_character))
+}
+
+/// The sum of the values of the first 15 characters of a code, before taking the
+/// reminder of its division by 26. See [check_character_breakdown].
+pub fn check_character_sum(first_15: &str) -> Result<u32, FiscalCodeError> {
+    let (contributions, _, _) = check_character_breakdown(first_15)?;
+
+    Ok(contributions.iter().map(|&value| u32::from(value)).sum())
+}
+
+/// The value of each character in an odd position (the 1st, the 3rd and so on)
+/// of the check character sum, digits first and then letters. See [check_character_breakdown].
+pub fn check_char_odd_table() -> impl Iterator<Item = (char, u8)> {
+    CHECK_CHARACTER_ODD_REPLACEMENTS
+        .entries()
+        .map(|(&character, &value)| (character, value))
+}
+
+/// Same as [check_char_odd_table], for the characters in an even position.
+pub fn check_char_even_table() -> impl Iterator<Item = (char, u8)> {
+    CHECK_CHARACTER_EVEN_REPLACEMENTS
+        .entries()
+        .map(|(&character, &value)| (character, value))
+}
+
+/// List the positions of the code whose character doesn't fit the fiscal code layout.
+///
+/// Only the positional grammar is checked: the town database, the date and the
+/// check character are not. Omocodia letters are accepted where a digit is expected.
+/// Characters past the 16th are ignored, so the length should be checked separately.
+pub fn position_errors(code: &str) -> Vec<(usize, PositionKind)> {
+    // characters are uppercased one by one, `ß` would become `SS` and shift the positions
+    code.trim()
+        .chars()
+        .take(16)
+        .enumerate()
+        .filter_map(|(i, character)| {
+            // any character outside of ASCII is classified as a NUL byte, which fits nowhere
+            let byte = if character.is_ascii() {
+                character.to_ascii_uppercase() as u8
+            } else {
+                0
+            };
+            classify_position(i, byte)
+                .err()
+                .map(|expected| (i, expected))
+        })
+        .collect()
+}
+
+/// What [position_errors] expected to find at a wrong position.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[non_exhaustive]
+pub enum PositionKind {
+    /// A letter was expected (surname, name, month, town letter or check character).
+    ExpectedLetter,
+    /// A digit was expected, and the letter found is not one used for omocodia.
+    ExpectedDigit,
+    /// Either a digit or an omocodia letter was expected, but neither was found.
+    ExpectedLetterOrDigit,
+}
+
+impl fmt::Display for PositionKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}",
+            match &self {
+                PositionKind::ExpectedLetter => "a letter",
+                PositionKind::ExpectedDigit => "a digit",
+                PositionKind::ExpectedLetterOrDigit => "a digit or an omocodia letter",
+            }
+        )
+    }
+}
+
+/// A validated Italian Fiscal Code stored inline, without any heap allocation.
+///
+/// The code is kept normalized (trimmed and uppercase), with any omocodia alterations.
+/// Temporary codes are **not** supported.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct FiscalCodeStr([u8; 16]);
+
+impl FiscalCodeStr {
+    pub fn as_str(&self) -> &str {
+        // only ASCII characters get past validation
+        std::str::from_utf8(&self.0).expect("valid ASCII")
+    }
+
+    /// Display the code in lowercase, e.g. `gntmtt99c27h501f`.
+    ///
+    /// The result can be parsed back, since input is uppercased on the way in.
+    pub fn lowercase(&self) -> LowerFiscalCode<'_> {
+        LowerFiscalCode(self.as_str())
+    }
+}
+
+/// A display wrapper that shows a code in lowercase, see [FiscalCodeStr::lowercase].
+#[derive(Debug, Clone, Copy)]
+pub struct LowerFiscalCode<'a>(&'a str);
+
+impl fmt::Display for LowerFiscalCode<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0.to_ascii_lowercase())
+    }
+}
+
+impl TryFrom<&str> for FiscalCodeStr {
+    type Error = FiscalCodeError;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        let code = parse(s)?;
+        let mut bytes = [0u8; 16];
+        bytes.copy_from_slice(code.representation.as_bytes());
+        Ok(FiscalCodeStr(bytes))
+    }
+}
+
+impl TryFrom<String> for FiscalCodeStr {
+    type Error = FiscalCodeError;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        FiscalCodeStr::try_from(s.as_str())
+    }
+}
+
+impl std::str::FromStr for FiscalCodeStr {
+    type Err = FiscalCodeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        FiscalCodeStr::try_from(s)
+    }
+}
+
+impl std::ops::Deref for FiscalCodeStr {
+    type Target = str;
+
+    fn deref(&self) -> &Self::Target {
+        self.as_str()
+    }
+}
+
+impl AsRef<str> for FiscalCodeStr {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl From<FiscalCodeStr> for String {
+    fn from(code: FiscalCodeStr) -> Self {
+        code.as_str().into()
+    }
+}
+
+impl fmt::Display for FiscalCodeStr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct FiscalCodeInfo {
+    pub born_on: NaiveDate,
+    pub gender: Gender,
+    pub place_of_birth: PlaceOfBirth,
+    /// Whether the birth year could be in both the 1900s and the 2000s,
+    /// in which case `born_on` holds the most recent past date and is a best guess.
+    pub year_is_ambiguous: bool,
+}
+
+impl FiscalCodeInfo {
+    /// The month of birth, as a typed value for formatting.
+    pub fn birth_month(&self) -> Month {
+        Month::try_from(self.born_on.month() as u8).expect("valid month")
+    }
+
+    /// The info as ordered pairs of field name and value, e.g. for templating.
+    ///
+    /// All the fields are always present, in the same order: a missing city or state
+    /// is an empty string.
+    pub fn to_fields(&self) -> Vec<(&'static str, String)> {
+        vec![
+            ("born_on", self.born_on.to_string()),
+            ("gender", self.gender.to_string()),
+            ("country", self.place_of_birth.country_name.clone()),
+            ("country_code", self.place_of_birth.country_code.clone()),
+            ("city", self.place_of_birth.city.clone().unwrap_or_default()),
+            (
+                "state",
+                self.place_of_birth.state.clone().unwrap_or_default(),
+            ),
+            ("year_is_ambiguous", self.year_is_ambiguous.to_string()),
+        ]
+    }
+
+    /// Guess the residency situation of the owner of the code from the place of birth.
+    ///
+    /// This is only a heuristic: the code doesn't record citizenship, so everyone born
+    /// abroad is assumed to be a foreigner. Use [FiscalCodeInfo::residency_hint_for_citizen]
+    /// when citizenship is known from somewhere else.
+    pub fn residency_hint(&self) -> ResidencyHint {
+        self.residency_hint_for_citizen(false)
+    }
+
+    /// Same as [FiscalCodeInfo::residency_hint], knowing whether the owner is an Italian citizen.
+    pub fn residency_hint_for_citizen(&self, italian_citizen: bool) -> ResidencyHint {
+        if self.place_of_birth.country_code == "IT" {
+            ResidencyHint::BornInItaly
+        } else if italian_citizen {
+            ResidencyHint::BornAbroadItalian
+        } else {
+            ResidencyHint::Foreigner
+        }
+    }
+}
+
+/// An indented block with the birth date, the gender and the place of birth,
+/// one per line.
+impl fmt::Display for FiscalCodeInfo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "\tBorn on: {}\n\tGender: {}\n\t{}",
+            self.born_on, self.gender, self.place_of_birth
+        )
+    }
+}
+
+/// See [FiscalCodeInfo::residency_hint].
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[non_exhaustive]
+pub enum ResidencyHint {
+    BornInItaly,
+    BornAbroadItalian,
+    Foreigner,
+}
+
+/// The gender encoded in the birth day of a code.
+///
+/// It reflects how the code was issued, which only allows two values, and says nothing
+/// about the identity of a person: it's always known for a code, so there is no variant for
+/// an unspecified gender. Where it may be missing, e.g. for incomplete records, use an
+/// `Option<Gender>`.
+///
+/// Like the other public enums of the crate, it's non-exhaustive:
+/// a `match` needs a wildcard arm.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[non_exhaustive]
+pub enum Gender {
+    #[cfg_attr(feature = "serde", serde(rename = "F"))]
+    Female,
+    #[cfg_attr(feature = "serde", serde(rename = "M"))]
+    Male,
+}
+
+impl Gender {
+    /// A compact encoding, e.g. for database columns: `0` for female and `1` for male.
+    pub fn as_u8(&self) -> u8 {
+        match self {
+            Gender::Female => 0,
+            Gender::Male => 1,
+        }
+    }
+
+    /// The inverse of [Gender::as_u8], `None` for unknown values.
+    pub fn from_u8(value: u8) -> Option<Self> {
+        match value {
+            0 => Some(Gender::Female),
+            1 => Some(Gender::Male),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Gender {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}",
+            match &self {
+                Gender::Female => "F",
+                Gender::Male => "M",
+            }
+        )
+    }
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct PlaceOfBirth {
+    pub country_code: String,
+    pub country_name: String,
+    pub city: Option<String>,
+    pub state: Option<String>,
+    /// The continent of a foreign place of birth, e.g. `Africa`, as named in the dataset.
+    ///
+    /// This is `None` for towns in Italy, and for countries without a continent in the dataset.
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub continent: Option<String>,
+    /// Whether the code had a placeholder instead of a town, see [info_with_placeholder_towns].
+    ///
+    /// The place of birth is then unknown: the country code and name are empty.
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "std::ops::Not::not"))]
+    pub is_placeholder: bool,
+}
+
+impl From<&Location<'_>> for PlaceOfBirth {
+    fn from(location: &Location<'_>) -> Self {
+        PlaceOfBirth {
+            country_code: location.country_code.into(),
+            country_name: location.country_name.into(),
+            city: location.city.map(|v| v.into()),
+            state: location.state.map(|v| v.into()),
+            continent: location.continent.map(|v| v.into()),
+            is_placeholder: false,
+        }
+    }
+}
+
+impl PlaceOfBirth {
+    /// A town in Italy, e.g. `PlaceOfBirth::italian("Roma", "RM")`.
+    pub fn italian(city: &str, state: &str) -> Self {
+        PlaceOfBirth {
+            country_code: "IT".into(),
+            country_name: "Italia".into(),
+            city: Some(city.into()),
+            state: Some(state.into()),
+            continent: None,
+            is_placeholder: false,
+        }
+    }
+
+    /// A foreign country, e.g. `PlaceOfBirth::foreign("SM", "San Marino")`.
+    ///
+    /// The continent is taken from the dataset entry of the country, if any.
+    pub fn foreign(country_code: &str, country_name: &str) -> Self {
+        let continent = BIRTH_TOWNS
+            .values()
+            .find(|location| location.country_code == country_code)
+            .and_then(|location| location.continent);
+
+        PlaceOfBirth {
+            country_code: country_code.into(),
+            country_name: country_name.into(),
+            city: None,
+            state: None,
+            continent: continent.map(|v| v.into()),
+            is_placeholder: false,
+        }
+    }
+
+    /// An unknown place of birth, see [PlaceOfBirth::is_placeholder].
+    fn placeholder() -> Self {
+        PlaceOfBirth {
+            country_code: String::new(),
+            country_name: String::new(),
+            city: None,
+            state: None,
+            continent: None,
+            is_placeholder: true,
+        }
+    }
+
+    /// Whether the place of birth is San Marino or Vatican City,
+    /// whose residents are commonly issued an Italian Fiscal Code.
+    pub fn is_microstate_birth(&self) -> bool {
+        matches!(self.country_code.as_str(), "SM" | "VA")
+    }
+}
+
+impl fmt::Display for PlaceOfBirth {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Country: {} ({})\n\tCity: {} ({})",
+            self.country_name,
+            self.country_code,
+            self.city.clone().unwrap_or("N/A".into()),
+            self.state.clone().unwrap_or("N/A".into())
+        )
+    }
+}
+
+/// Only the first 15 characters are considered, so the code may or may not
+/// include its check character.
+fn calculate_check_character(code: &str) -> char {
+    let sum: u32 = check_character_contributions(code)
+        .iter()
+        .map(|&value| u32::from(value))
+        .sum();
+
+    CHECK_CHARACTER_REMINDER
+        .get(&((sum % 26) as u8))
+        .copied()
+        .expect("value replacement found")
+}
+
+/// The value of each of the first 15 characters in the check character sum.
+fn check_character_contributions(code: &str) -> Vec<u8> {
+    code.char_indices()
+        .take(15)
+        .map(|(i, character)| {
+            let replacements = if (i + 1) % 2 == 0 {
+                &CHECK_CHARACTER_EVEN_REPLACEMENTS
+            } else {
+                &CHECK_CHARACTER_ODD_REPLACEMENTS
+            };
+            *replacements
+                .get(&character)
+                .expect("character replacement found")
+        })
+        .collect()
+}
+
+fn calculate_check_character_temporary(code: &str) -> char {
+    let digits: Vec<u8> = code
+        .chars()
+        .map(|c| c.to_digit(10).expect("valid digit") as u8)
+        .collect();
+
+    let odd_sum: u8 = digits.iter().step_by(2).sum();
+    let even_sum: u8 = digits
+        .iter()
+        .skip(1)
+        .step_by(2)
+        .map(|&digit| {
+            let doubled = digit * 2;
+            if doubled >= 10 {
+                doubled - 9
+            } else {
+                doubled
+            }
+        })
+        .sum();
+
+    let total = odd_sum + even_sum;
+    let units = total % 10;
+    ((10 - units) % 10 + 48) as char
+}
+
+/// A valid Italian Fiscal Code, with all of its fields decoded. See [parse].
+#[derive(Debug, Clone)]
+pub struct FiscalCode {
+    /// The string representing this code
+    representation: String,
+    /// The string representing this code without any omocodia alterations
+    representation_canonical: String,
+    surname: String,
+    name: String,
+    born_on: NaiveDate,
+    gender: Gender,
+    place_of_birth: PlaceOfBirth,
+    year_is_ambiguous: bool,
+}
+
+impl TryFrom<&str> for FiscalCode {
+    type Error = FiscalCodeError;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        FiscalCode::decode(s, &SystemClock, &HashMap::new(), &[])
+    }
+}
+
+impl std::str::FromStr for FiscalCode {
+    type Err = FiscalCodeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        FiscalCode::try_from(s)
+    }
+}
+
+impl FiscalCode {
+    /// The code, trimmed and uppercase, including any omocodia alterations.
+    pub fn representation(&self) -> &str {
+        &self.representation
+    }
+
+    /// The code without any omocodia alterations, with its own check character.
+    ///
+    /// It's always 16 characters long, with digits at all the digit positions:
+    /// `[A-Z]{6}[0-9]{2}[A-Z][0-9]{2}[A-Z][0-9]{3}[A-Z]`.
+    pub fn representation_canonical(&self) -> &str {
+        &self.representation_canonical
+    }
+
+    /// The three characters encoding the surname.
+    pub fn surname(&self) -> &str {
+        &self.surname
+    }
+
+    /// The three characters encoding the name.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The two-digit year as encoded in the code, before solving the century.
+    pub fn encoded_year(&self) -> u8 {
+        self.representation_canonical[6..8]
+            .parse()
+            .expect("digits at the year positions")
+    }
+
+    /// The letter encoding the month of birth, e.g. `C` for March.
+    pub fn birth_month_letter(&self) -> char {
+        self.representation_canonical.as_bytes()[8] as char
+    }
+
+    pub fn born_on(&self) -> NaiveDate {
+        self.born_on
+    }
+
+    pub fn gender(&self) -> &Gender {
+        &self.gender
+    }
+
+    pub fn place_of_birth(&self) -> &PlaceOfBirth {
+        &self.place_of_birth
+    }
+
+    /// See [FiscalCodeInfo::year_is_ambiguous].
+    pub fn year_is_ambiguous(&self) -> bool {
+        self.year_is_ambiguous
+    }
+
+    /// The decoded fields, without decoding the code again.
+    pub fn info(&self) -> FiscalCodeInfo {
+        FiscalCodeInfo {
+            born_on: self.born_on,
+            gender: self.gender.clone(),
+            place_of_birth: self.place_of_birth.clone(),
+            year_is_ambiguous: self.year_is_ambiguous,
+        }
+    }
+
+    /// Whether some digits of the code were replaced by letters because of omocodia.
+    pub fn is_omocodia(&self) -> bool {
+        self.representation[..15] != self.representation_canonical[..15]
+    }
+
+    /// Decode a code, solving the century of the birth year with the current date of `clock`.
+    /// The town is looked up in `extra_towns` first, then in the dataset, unless it's one of
+    /// the `placeholder_towns`: these are 4 uppercase letters or digits, see
+    /// [info_with_placeholder_towns].
+    fn decode(
+        s: &str,
+        clock: &dyn Clock,
+        extra_towns: &HashMap<String, Location>,
+        placeholder_towns: &[&str],
+    ) -> Result<Self, FiscalCodeError> {
+        let code = s.trim().to_ascii_uppercase();
+        if code.is_empty() {
+            return Err(FiscalCodeError::EmptyInput);
+        }
+        if !code.is_ascii() {
+            return Err(FiscalCodeError::NonAscii);
+        }
+        if code.len() != 16 {
+            return Err(FiscalCodeError::InvalidLength);
+        }
+        // a single pass checks the kind of each character, reverts omocodia and sums the
+        // values for the check character, both of the code and of its canonical version
+        let mut canonical = [0u8; 16];
+        let mut sum = 0u32;
+        let mut sum_canonical = 0u32;
+        let placeholder_town = placeholder_towns.contains(&&code[11..15]);
+        for (i, &byte) in code.as_bytes().iter().enumerate() {
+            // placeholders are taken as they are, with no omocodia to revert
+            if placeholder_town && (11..15).contains(&i) && byte.is_ascii_alphanumeric() {
+                canonical[i] = byte;
+            } else {
+                canonical[i] = classify_position(i, byte).map_err(|expected| {
+                    if i < 6 {
+                        FiscalCodeError::InvalidNameBlock { position: i }
+                    } else {
+                        FiscalCodeError::InvalidCharacter {
+                            position: i,
+                            expected,
+                        }
+                    }
+                })?;
+            }
+
+            if i < 15 {
+                let values = &CHECK_CHARACTER_VALUES[i % 2];
+                sum += u32::from(values[byte as usize]);
+                sum_canonical += u32::from(values[canonical[i] as usize]);
+            }
+        }
+        let check_character = |sum: u32| {
+            CHECK_CHARACTER_REMINDER
+                .get(&((sum % 26) as u8))
+                .copied()
+                .expect("value replacement found")
+        };
+        let check_character_calculated = check_character(sum);
+        let check_character_actual = code.as_bytes()[15] as char;
+        if check_character_actual != check_character_calculated {
+            return Err(FiscalCodeError::InvalidCheckCharacter {
+                found: check_character_actual,
+                expected: check_character_calculated,
+            });
+        }
+        // the check character has to be recomputed over the restored digits
+        canonical[15] = check_character(sum_canonical) as u8;
+        check_canonical(&canonical, placeholder_town)?;
+        // the guard above only lets ASCII letters and digits through
+        let code_canonical = String::from_utf8(canonical.to_vec()).expect("ASCII code");
+
+        // and the year and day positions hold digits, two of them can't overflow a u8
+        let digits = |range: std::ops::Range<usize>| {
+            code_canonical[range]
+                .parse::<u8>()
+                .expect("digits checked above")
+        };
+        let birth_year = digits(6..8);
+        let birth_month = code_canonical.as_bytes()[8] as char;
+        let birth_day = BirthDay::from_encoded(digits(9..11))?;
+        let birth_town = &code_canonical[11..15];
+        let born_on = born_on(birth_year, birth_month, birth_day.day(), clock.today())?;
+        let year_is_ambiguous = [1900, 2000]
+            .iter()
+            .all(|century| born_on.with_year(century + birth_year as i32).is_some());
+
+        Ok(FiscalCode {
+            surname: code[..3].into(),
+            name: code[3..6].into(),
+            representation: code,
+            born_on,
+            gender: birth_day.gender().clone(),
+            place_of_birth: match extra_towns.get(birth_town) {
+                _ if placeholder_town => PlaceOfBirth::placeholder(),
+                Some(location) => location.into(),
+                None => place_of_birth(birth_town, born_on.year())?,
+            },
+            year_is_ambiguous,
+            representation_canonical: code_canonical,
+        })
+    }
+}
+
+/// Check that a canonical code has the grammar documented on
+/// [FiscalCode::representation_canonical], except for the town of a placeholder.
+///
+/// The decoding already restores a digit at each omocodia position, this guards the
+/// reversal with an error rather than a panic further on.
+fn check_canonical(canonical: &[u8; 16], placeholder_town: bool) -> Result<(), FiscalCodeError> {
+    for (i, &byte) in canonical.iter().enumerate() {
+        if placeholder_town && (11..15).contains(&i) {
+            continue;
+        }
+        let (valid, expected) = if OMOCODIA_INDICES.contains(&i) {
+            (byte.is_ascii_digit(), PositionKind::ExpectedDigit)
+        } else {
+            (byte.is_ascii_uppercase(), PositionKind::ExpectedLetter)
+        };
+        if !valid {
+            return Err(FiscalCodeError::InvalidCharacter {
+                position: i,
+                expected,
+            });
+        }
+    }
+
+    Ok(())
+}
+
+fn born_on(
+    birth_year: u8,
+    birth_month: char,
+    day: u8,
+    today: NaiveDate,
+) -> Result<NaiveDate, FiscalCodeError> {
+    let month = *BIRTH_MONTHS
+        .entries()
+        .find(|(_, &c)| c == birth_month)
+        .ok_or(FiscalCodeError::InvalidBirthMonth)?
+        .0
+        + 1;
+
+    // the most recent century that doesn't put the birth after today
+    let year = {
+        let year = today.year().div_euclid(100) * 100 + birth_year as i32;
+
+        if (year, u32::from(month), u32::from(day)) > (today.year(), today.month(), today.day()) {
+            year - 100
+        } else {
+            year
+        }
+    };
+
+    NaiveDate::from_ymd_opt(year, month.into(), day.into()).ok_or(
+        FiscalCodeError::InvalidBirthDate {
+            day,
+            month: Month::try_from(month).expect("valid month"),
+            year,
+        },
+    )
+}
+
+fn place_of_birth(birth_town: &str, birth_year: i32) -> Result<PlaceOfBirth, FiscalCodeError> {
+    // most codes have a single location, which is looked up once
+    let location = match BIRTH_TOWN_HISTORY.get(birth_town) {
+        Some(locations) => location_in(&locations.iter().collect::<Vec<_>>(), birth_year)
+            .or_else(|| lookup_town(birth_town)),
+        None => lookup_town(birth_town),
+    };
+
+    location
+        .map(PlaceOfBirth::from)
+        .ok_or(FiscalCodeError::InvalidBirthTown)
+}
+
+/// Whether all the locations of a code were introduced after `year`.
+fn born_before_town(locations: &[&Location], year: i32) -> bool {
+    !locations.is_empty()
+        && locations
+            .iter()
+            .all(|location| location.valid_from.is_some_and(|from| year < from))
+}
+
+/// Pick the location valid in `year`, a missing bound is considered open.
+fn location_in<'a>(locations: &[&'a Location<'a>], year: i32) -> Option<&'a Location<'a>> {
+    locations
+        .iter()
+        .find(|location| {
+            location.valid_from.is_none_or(|from| from <= year)
+                && location.valid_to.is_none_or(|to| year <= to)
+        })
+        .copied()
+}
+
+/// Positions of the digits that may be replaced by a letter in case of omocodia
+const OMOCODIA_INDICES: [usize; 7] = [6, 7, 9, 10, 12, 13, 14];
+
+/// What a position of a code holds.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum CharClass {
+    Letter,
+    /// A digit, or the omocodia letter replacing it.
+    Digit,
+}
+
+/// The class of each position of a code, see [OMOCODIA_INDICES].
+const POSITION_CLASSES: [CharClass; 16] = {
+    let mut classes = [CharClass::Letter; 16];
+    let mut i = 0;
+    while i < OMOCODIA_INDICES.len() {
+        classes[OMOCODIA_INDICES[i]] = CharClass::Digit;
+        i += 1;
+    }
+    classes
+};
+
+/// Whether each byte is an uppercase ASCII letter.
+const UPPERCASE: [bool; 256] = {
+    let mut table = [false; 256];
+    let mut byte = b'A';
+    while byte <= b'Z' {
+        table[byte as usize] = true;
+        byte += 1;
+    }
+    table
+};
+
+/// The digit each byte stands for at a digit position: itself for a digit,
+/// the replaced one for an omocodia letter. Same as [DIGIT_REPLACEMENTS], reversed.
+const DIGIT_VALUES: [Option<u8>; 256] = {
+    let mut table = [None; 256];
+    let letters = b"LMNPQRSTUV";
+    let mut digit = 0;
+    while digit < 10 {
+        table[(b'0' + digit) as usize] = Some(digit);
+        table[letters[digit as usize] as usize] = Some(digit);
+        digit += 1;
+    }
+    table
+};
+
+/// Check the byte at position `i` of an uppercase code, returning its canonical version,
+/// i.e. with omocodia reverted, or what was expected instead.
+fn classify_position(i: usize, byte: u8) -> Result<u8, PositionKind> {
+    match POSITION_CLASSES[i] {
+        CharClass::Letter if UPPERCASE[byte as usize] => Ok(byte),
+        CharClass::Letter => Err(PositionKind::ExpectedLetter),
+        CharClass::Digit => match DIGIT_VALUES[byte as usize] {
+            Some(digit) => Ok(b'0' + digit),
+            None if UPPERCASE[byte as usize] => Err(PositionKind::ExpectedDigit),
+            None => Err(PositionKind::ExpectedLetterOrDigit),
+        },
+    }
+}
+
+static BIRTH_MONTHS: phf::OrderedMap<u8, char> = phf_ordered_map! {
+    0u8 => 'A',
+    1u8 => 'B',
+    2u8 => 'C',
+    3u8 => 'D',
+    4u8 => 'E',
+    5u8 => 'H',
+    6u8 => 'L',
+    7u8 => 'M',
+    8u8 => 'P',
+    9u8 => 'R',
+    10u8 => 'S',
+    11u8 => 'T',
+};
+
+static DIGIT_REPLACEMENTS: phf::OrderedMap<u8, char> = phf_ordered_map! {
+   0u8 => 'L',
+   1u8 => 'M',
+   2u8 => 'N',
+   3u8 => 'P',
+   4u8 => 'Q',
+   5u8 => 'R',
+   6u8 => 'S',
+   7u8 => 'T',
+   8u8 => 'U',
+   9u8 => 'V',
+};
+
+static CHECK_CHARACTER_ODD_REPLACEMENTS: phf::OrderedMap<char, u8> = phf_ordered_map! {
+   '0' => 1u8,
+   '1' => 0u8,
+   '2' => 5u8,
+   '3' => 7u8,
+   '4' => 9u8,
+   '5' => 13u8,
+   '6' => 15u8,
+   '7' => 17u8,
+   '8' => 19u8,
+   '9' => 21u8,
+   'A' => 1u8,
+   'B' => 0u8,
+   'C' => 5u8,
+   'D' => 7u8,
+   'E' => 9u8,
+   'F' => 13u8,
+   'G' => 15u8,
+   'H' => 17u8,
+   'I' => 19u8,
+   'J' => 21u8,
+   'K' => 2u8,
+   'L' => 4u8,
+   'M' => 18u8,
+   'N' => 20u8,
+   'O' => 11u8,
+   'P' => 3u8,
+   'Q' => 6u8,
+   'R' => 8u8,
+   'S' => 12u8,
+   'T' => 14u8,
+   'U' => 16u8,
+   'V' => 10u8,
+   'W' => 22u8,
+   'X' => 25u8,
+   'Y' => 24u8,
+   'Z' => 23u8,
+};
+
+static CHECK_CHARACTER_EVEN_REPLACEMENTS: phf::OrderedMap<char, u8> = phf_ordered_map! {
+   '0' => 0u8,
+   '1' => 1u8,
+   '2' => 2u8,
+   '3' => 3u8,
+   '4' => 4u8,
+   '5' => 5u8,
+   '6' => 6u8,
+   '7' => 7u8,
+   '8' => 8u8,
+   '9' => 9u8,
+   'A' => 0u8,
+   'B' => 1u8,
+   'C' => 2u8,
+   'D' => 3u8,
+   'E' => 4u8,
+   'F' => 5u8,
+   'G' => 6u8,
+   'H' => 7u8,
+   'I' => 8u8,
+   'J' => 9u8,
+   'K' => 10u8,
+   'L' => 11u8,
+   'M' => 12u8,
+   'N' => 13u8,
+   'O' => 14u8,
+   'P' => 15u8,
+   'Q' => 16u8,
+   'R' => 17u8,
+   'S' => 18u8,
+   'T' => 19u8,
+   'U' => 20u8,
+   'V' => 21u8,
+   'W' => 22u8,
+   'X' => 23u8,
+   'Y' => 24u8,
+   'Z' => 25u8,
+};
+
+/// The values of [CHECK_CHARACTER_ODD_REPLACEMENTS] and [CHECK_CHARACTER_EVEN_REPLACEMENTS],
+/// in this order, indexed by byte for the decoding hot path.
+static CHECK_CHARACTER_VALUES: LazyLock<[[u8; 256]; 2]> = LazyLock::new(|| {
+    let mut tables = [[0; 256]; 2];
+    for (table, replacements) in tables.iter_mut().zip([
+        &CHECK_CHARACTER_ODD_REPLACEMENTS,
+        &CHECK_CHARACTER_EVEN_REPLACEMENTS,
+    ]) {
+        for (&character, &value) in replacements.entries() {
+            table[character as usize] = value;
+        }
+    }
+    tables
+});
+
+static CHECK_CHARACTER_REMINDER: phf::OrderedMap<u8, char> = phf_ordered_map! {
+   0u8 => 'A',
+   1u8 => 'B',
+   2u8 => 'C',
+   3u8 => 'D',
+   4u8 => 'E',
+   5u8 => 'F',
+   6u8 => 'G',
+   7u8 => 'H',
+   8u8 => 'I',
+   9u8 => 'J',
+   10u8 => 'K',
+   11u8 => 'L',
+   12u8 => 'M',
+   13u8 => 'N',
+   14u8 => 'O',
+   15u8 => 'P',
+   16u8 => 'Q',
+   17u8 => 'R',
+   18u8 => 'S',
+   19u8 => 'T',
+   20u8 => 'U',
+   21u8 => 'V',
+   22u8 => 'W',
+   23u8 => 'X',
+   24u8 => 'Y',
+   25u8 => 'Z',
+};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate() {
+        //spell-checker: disable
+        assert!(validate("GNTMTT99C27H501F"));
+        assert!(validate("MRARSS80A01H501T"));
+        assert!(validate("BNCLRD69T61A783M"));
+        assert!(validate("FCKTSS05C01Z122F"));
+        assert!(validate("FCKTSS05C01ZMLQH"));
+
+        assert!(!validate("INVALIDCODE"));
+        assert!(!validate("FCKTSS05C01Z122K"));
+        assert!(!validate("FCKTSS05F01Z122F"));
+        assert!(!validate("FCKTSS05C32Z122F"));
+        assert!(!validate("FCKTSS05C01Z105L"));
+        assert!(!validate("GNTMTT99C72H501Y"));
+        //spell-checker: enable
+    }
+
+    #[test]
+    fn test_validate_errors() {
+        //spell-checker: disable
+        assert_eq!(
+            validate_or_error("GNTMTT99C27H501F1"),
+            Err(FiscalCodeError::InvalidLength)
+        );
+        assert_eq!(
+            validate_or_error("GNTMTT99C27H5ÈF"),
+            Err(FiscalCodeError::NonAscii)
+        );
+        // `ß` is not uppercased to `SS`, which would make this a valid code
+        assert_eq!(
+            validate_or_error("ßTMTT99C27H501H"),
+            Err(FiscalCodeError::NonAscii)
+        );
+        // full-width and Arabic-Indic digits are not temporary codes
+        assert_eq!(
+            validate_or_error("１２３４５６７８９０３"),
+            Err(FiscalCodeError::NonAscii)
+        );
+        assert_eq!(
+            validate_or_error("١٢٣٤٥٦٧٨٩٠٣"),
+            Err(FiscalCodeError::NonAscii)
+        );
+        assert_eq!(
+            check("１２３４５６７８９０３"),
+            Validity::Invalid(FiscalCodeError::NonAscii)
+        );
+        assert_eq!(
+            validate_or_error("GNTM0T99C27H501F"),
+            Err(FiscalCodeError::InvalidNameBlock { position: 4 })
+        );
+        assert_eq!(
+            validate_or_error("GNTMTT99C27H50-F"),
+            Err(FiscalCodeError::InvalidCharacter {
+                position: 14,
+                expected: PositionKind::ExpectedLetterOrDigit
+            })
+        );
+        assert_eq!(
+            validate_or_error("GNTMTT99C27H501K"),
+            Err(FiscalCodeError::InvalidCheckCharacter {
+                found: 'K',
+                expected: 'F'
+            })
+        );
+        assert_eq!(
+            validate_or_error("FCKTSS05F01Z122N"),
+            Err(FiscalCodeError::InvalidBirthMonth)
+        );
+        assert_eq!(
+            validate_or_error("FCKTSS05C32Z122N"),
+            Err(FiscalCodeError::InvalidBirthDay { encoded: 32 })
+        );
+        assert_eq!(
+            validate_or_error("FCKTSS05B30Z122E"),
+            Err(FiscalCodeError::InvalidBirthDate {
+                day: 30,
+                month: Month::February,
+                year: 2005
+            })
+        );
+        assert_eq!(
+            validate_or_error("FCKTSS05D31Z122K")
+                .unwrap_err()
+                .to_string(),
+            "Day 31 invalid for month April"
+        );
+        assert_eq!(
+            validate_or_error("FCKTSS05B29Z122X")
+                .unwrap_err()
+                .to_string(),
+            "Day 29 invalid for month February in 2005"
+        );
+        assert_eq!(
+            validate_or_error("FCKTSS05C01Z000A"),
+            Err(FiscalCodeError::InvalidBirthTown)
+        );
+        assert_eq!(
+            validate_or_error("12345678904"),
+            Err(FiscalCodeError::InvalidTemporaryCheckCharacter)
+        );
+        //spell-checker: enable
+    }
+
+    #[test]
+    fn test_check() {
+        //spell-checker: disable
+        assert_eq!(check("GNTMTT99C27H501F"), Validity::ValidPersonal);
+        assert_eq!(check("gntmtt99c27hrlmd"), Validity::ValidPersonal);
+        assert_eq!(check(" 12345678903 "), Validity::ValidTemporary);
+        assert_eq!(
+            check("12345678904"),
+            Validity::Invalid(FiscalCodeError::InvalidTemporaryCheckCharacter)
+        );
+        assert_eq!(
+            check("GNTMTT99C27H501K"),
+            Validity::Invalid(FiscalCodeError::InvalidCheckCharacter {
+                found: 'K',
+                expected: 'F'
+            })
+        );
+        //spell-checker: enable
+    }
+
+    #[test]
+    fn test_validate_normalized() {
+        //spell-checker: disable
+        assert_eq!(
+            validate_normalized(" gntmtt99c27hrlmd\n").unwrap(),
+            "GNTMTT99C27HRLMD"
+        );
+        assert_eq!(validate_normalized(" 12345678903 ").unwrap(), "12345678903");
+        assert_eq!(
+            validate_normalized("GNTMTT99C27H501K"),
+            Err(FiscalCodeError::InvalidCheckCharacter {
+                found: 'K',
+                expected: 'F'
+            })
+        );
+        //spell-checker: enable
+    }
+
+    #[test]
+    fn test_validate_canonical_only() {
+        //spell-checker: disable
+        assert_eq!(validate_canonical_only("GNTMTT99C27H501F"), Ok(()));
+        assert_eq!(
+            validate_canonical_only("GNTMTT99C27HRLMD"),
+            Err(FiscalCodeError::OmocodiaNotAllowed)
+        );
+        assert!(validate("GNTMTT99C27HRLMD"));
+        assert_eq!(
+            validate_canonical_only("GNTMTT99C27HRLMF"),
+            Err(FiscalCodeError::InvalidCheckCharacter {
+                found: 'F',
+                expected: 'D'
+            })
+        );
+        //spell-checker: enable
+    }
+
+    #[test]
+    fn test_canonicalize() {
+        //spell-checker: disable
+        assert_eq!(
+            canonicalize("GNTMTT99C27H501F").unwrap(),
+            "GNTMTT99C27H501F"
+        );
+        assert_eq!(
+            canonicalize("gntmtt99c27hrlmd").unwrap(),
+            "GNTMTT99C27H501F"
+        );
+        assert!(canonicalize("GNTMTT99C27HRLMF").is_err());
+        //spell-checker: enable
+    }
+
+    #[test]
+    fn test_extract_codes() {
+        //spell-checker: disable
+        assert_eq!(
+            extract_codes("Il codice è GNTMTT99C27H501F, grazie"),
+            ["GNTMTT99C27H501F"]
+        );
+        assert_eq!(
+            extract_codes("gntmtt99c27hrlmd;GNTMTT99C27H501K (RSSMRA85T10H501O)"),
+            ["GNTMTT99C27H501F", "RSSMRA85T10H501O"]
+        );
+        assert!(extract_codes("XGNTMTT99C27H501F GNTMTT99C27H501FX").is_empty());
+        assert!(extract_codes("nessun codice").is_empty());
+        //spell-checker: enable
+    }
+
+    #[test]
+    fn test_aggregate_by_province() {
+        //spell-checker: disable
+        let codes = [
+            "GNTMTT99C27H501F",
+            "GNTMTT99C27HRLMD",
+            "RSSMRA85T10A000W",
+            "FCKTSS05C01Z130C",
+            "GNTMTT99C27H501K",
+            "12345678903",
+        ];
+        //spell-checker: enable
+        assert_eq!(
+            aggregate_by_province(&codes),
+            HashMap::from([("RM".to_string(), 2)])
+        );
+        assert!(aggregate_by_province(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_find_invalid() {
+        //spell-checker: disable
+        let codes = [
+            "GNTMTT99C27H501F",
+            "GNTMTT99C27H501K",
+            "12345678903",
+            "TOOSHORT",
+        ];
+        assert_eq!(
+            validate_batch(&codes),
+            vec![
+                Ok(()),
+                Err(FiscalCodeError::InvalidCheckCharacter {
+                    found: 'K',
+                    expected: 'F'
+                }),
+                Ok(()),
+                Err(FiscalCodeError::InvalidLength),
+            ]
+        );
+        assert_eq!(
+            find_invalid(&codes),
+            vec![
+                (
+                    1,
+                    "GNTMTT99C27H501K".to_string(),
+                    FiscalCodeError::InvalidCheckCharacter {
+                        found: 'K',
+                        expected: 'F'
+                    }
+                ),
+                (3, "TOOSHORT".to_string(), FiscalCodeError::InvalidLength),
+            ]
+        );
+        //spell-checker: enable
+        assert!(find_invalid(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_validate_omocodia() {
+        //spell-checker: disable
+        assert!(validate("GNTMTT99C27H50MX"));
+        assert!(validate("GNTMTT99C27HR0MS"));
+        //spell-checker: enable
+    }
+
+    #[test]
+    fn test_gender_day_boundaries() {
+        let date = |y, m, d| NaiveDate::from_ymd_opt(y, m, d).unwrap();
+
+        //spell-checker: disable
+        for (code, born_on, gender) in [
+            ("GNTMTT99C01H501M", date(1999, 3, 1), Gender::Male),
+            ("GNTMTT99C31H501P", date(1999, 3, 31), Gender::Male),
+            ("GNTMTT99C41H501Q", date(1999, 3, 1), Gender::Female),
+            ("GNTMTT99C71H501T", date(1999, 3, 31), Gender::Female),
+        ] {
+            let code = parse(code).unwrap();
+            assert_eq!(code.born_on(), born_on);
+            assert_eq!(code.gender(), &gender);
+        }
+
+        for (code, encoded) in [
+            ("GNTMTT99C00H501N", 0),
+            ("GNTMTT99C32H501U", 32),
+            ("GNTMTT99C40H501R", 40),
+            ("GNTMTT99C72H501Y", 72),
+            ("GNTMTT99C81H501U", 81),
+            ("GNTMTT99C99H501Q", 99),
+        ] {
+            assert_eq!(
+                validate_or_error(code),
+                Err(FiscalCodeError::InvalidBirthDay { encoded })
+            );
+        }
+
+        assert_eq!(
+            FiscalCodeError::InvalidBirthDay { encoded: 81 }.to_string(),
+            "Invalid birth day: 81, female days go up to 71"
+        );
+        assert_eq!(
+            FiscalCodeError::InvalidBirthDay { encoded: 35 }.to_string(),
+            "Invalid birth day: 35, male days go up to 31 and female days start at 41"
+        );
+        assert_eq!(
+            FiscalCodeError::InvalidBirthDay { encoded: 0 }.to_string(),
+            "Invalid birth day: 00"
+        );
+        //spell-checker: enable
+    }
+
+    #[test]
+    fn test_fiscal_code_info() {
+        //spell-checker: disable
+        let code = parse("GNTMTT99C27HRLMD").unwrap();
+        let info = code.info();
+        //spell-checker: enable
+        assert_eq!(info.born_on, code.born_on());
+        assert_eq!(&info.gender, code.gender());
+        assert_eq!(info.place_of_birth.city, code.place_of_birth().city);
+        assert_eq!(info.year_is_ambiguous, code.year_is_ambiguous());
+    }
+
+    #[test]
+    fn test_representation_canonical_grammar() {
+        let regex = Regex::new(patterns::CANONICAL_CODE).unwrap();
+
+        //spell-checker: disable
+        let code = parse("GNTMTTVVCNTHRLMM").unwrap();
+        assert_eq!(code.representation_canonical(), "GNTMTT99C27H501F");
+        for variant in omocodia_variants("GNTMTT99C27H501F").unwrap() {
+            let canonical = parse(&variant).unwrap().representation_canonical;
+            assert_eq!(canonical.len(), 16);
+            assert!(regex.is_match(&canonical), "{}", canonical);
+        }
+        //spell-checker: enable
+    }
+
+    #[test]
+    fn test_check_canonical() {
+        //spell-checker: disable
+        assert_eq!(check_canonical(b"GNTMTT99C27H501F", false), Ok(()));
+        assert_eq!(check_canonical(b"GNTMTT99C27XXXXF", true), Ok(()));
+        assert_eq!(
+            check_canonical(b"GNTMTT9VC27H501F", false),
+            Err(FiscalCodeError::InvalidCharacter {
+                position: 7,
+                expected: PositionKind::ExpectedDigit
+            })
+        );
+        assert_eq!(
+            check_canonical(b"GNTMTT99C27XXXXF", false),
+            Err(FiscalCodeError::InvalidCharacter {
+                position: 12,
+                expected: PositionKind::ExpectedDigit
+            })
+        );
+        assert_eq!(
+            check_canonical(b"GNTMTT99227H501F", false),
+            Err(FiscalCodeError::InvalidCharacter {
+                position: 8,
+                expected: PositionKind::ExpectedLetter
+            })
+        );
+        //spell-checker: enable
+    }
+
+    #[test]
+    fn test_encoded_year() {
+        //spell-checker: disable
+        assert_eq!(parse("GNTMTT99C27H501F").unwrap().encoded_year(), 99);
+        assert_eq!(parse("GNTMTTVVCNTHRLMM").unwrap().encoded_year(), 99);
+        assert_eq!(parse("RSSMRA05T10H501W").unwrap().encoded_year(), 5);
+        //spell-checker: enable
+    }
+
+    #[test]
+    fn test_birth_month_letter() {
+        //spell-checker: disable
+        assert_eq!(parse("GNTMTT99C27H501F").unwrap().birth_month_letter(), 'C');
+        assert_eq!(parse("RSSMRA85T10H501O").unwrap().birth_month_letter(), 'T');
+        //spell-checker: enable
+    }
+
+    #[test]
+    fn test_omocodia_town_digits() {
+        //spell-checker: disable
+        let code = FiscalCode::try_from("GNTMTT99C27HRLMD").unwrap();
+        assert_eq!(code.representation_canonical, "GNTMTT99C27H501F");
+        assert_eq!(code.place_of_birth.city, Some("Roma".into()));
+
+        let code = FiscalCode::try_from("GNTMTT99C27H50MX").unwrap();
+        assert_eq!(code.representation_canonical, "GNTMTT99C27H501F");
+
+        // the town letter is never altered, so it can't hold a digit
+        assert!(!validate("GNTMTT99C271501Z"));
+        //spell-checker: enable
+    }
+
+    #[test]
+    fn test_altered_positions() {
+        //spell-checker: disable
+        assert_eq!(
+            altered_positions("GNTMTT99C27H501F").unwrap(),
+            Vec::<usize>::new()
+        );
+        assert_eq!(altered_positions("GNTMTT99C27H50MX").unwrap(), vec![14]);
+        assert_eq!(
+            altered_positions("GNTMTT99C27HRLMD").unwrap(),
+            vec![12, 13, 14]
+        );
+        assert_eq!(
+            altered_positions("FCKTSS05C01Z122K"),
+            Err(FiscalCodeError::InvalidCheckCharacter {
+                found: 'K',
+                expected: 'F'
+            })
+        );
+        //spell-checker: enable
+    }
+
+    #[test]
+    fn test_born_on_matches() {
+        //spell-checker: disable
+        let date = |y, m, d| NaiveDate::from_ymd_opt(y, m, d).unwrap();
+        assert!(born_on_matches("GNTMTT99C27H501F", date(1999, 3, 27)).unwrap());
+        assert!(born_on_matches("GNTMTT99C27H501F", date(2099, 3, 27)).unwrap());
+        assert!(!born_on_matches("GNTMTT99C27H501F", date(1899, 3, 27)).unwrap());
+        assert!(!born_on_matches("GNTMTT99C27H501F", date(1999, 3, 28)).unwrap());
+        assert!(!born_on_matches("MKSKRS92L65Z219S", date(1992, 7, 5)).unwrap());
+        assert!(born_on_matches("MKSKRS92L65Z219S", date(1992, 7, 25)).unwrap());
+        assert!(born_on_matches("FCKTSS05C01Z122K", date(2005, 3, 1)).is_err());
+        //spell-checker: enable
+    }
+
+    #[test]
+    fn test_validate_provisional() {
+        assert!(validate("12345678903"));
+    }
+
+    #[test]
+    fn test_calculate_check_character_temporary() {
+        // Agenzia delle Entrate
+        assert_eq!(calculate_check_character_temporary("0636339100"), '1');
+        // Telecom Italia and Enel, where the sum is a multiple of 10
+        assert_eq!(calculate_check_character_temporary("0048841001"), '0');
+        assert_eq!(calculate_check_character_temporary("0081172058"), '0');
+        assert_eq!(calculate_check_character_temporary("0000000000"), '0');
+        // the largest possible sum
+        assert_eq!(calculate_check_character_temporary("9999999999"), '0');
+        assert_eq!(calculate_check_character_temporary("0000000001"), '8');
+
+        assert!(validate("06363391001"));
+        assert!(validate("00488410010"));
+        assert!(validate("00000000000"));
+        assert!(!validate("06363391002"));
+        assert!(!validate("00488410011"));
+        assert!(!validate("12345678930"));
+    }
+
+    #[test]
+    fn test_validate_empty() {
+        assert!(!validate(""));
+    }
+
+    #[test]
+    fn test_validate_invalid_length() {
+        // spell-checker: disable
+        assert!(!validate("TOOSHORT"));
+        assert!(!validate("THISCODEISTOOLONGTOBEAVALIDFISCALCODE"));
+        //spell-checker: enable
+    }
+
+    #[test]
+    fn test_birthplace_matches() {
+        //spell-checker: disable
+        assert!(birthplace_matches("GNTMTT99C27H501F", "H501").unwrap());
+        assert!(birthplace_matches("GNTMTT99C27HRLMD", " h501").unwrap());
+        assert!(!birthplace_matches("GNTMTT99C27H501F", "H50M").unwrap());
+        assert!(!birthplace_matches("GNTMTT99C27H501F", "F205").unwrap());
+        assert!(birthplace_matches("GNTMTT99C27H501K", "H501").is_err());
+        //spell-checker: enable
+    }
+
+    #[test]
+    fn test_birth_key() {
+        //spell-checker: disable
+        assert_eq!(birth_key("GNTMTT99C27H501F").unwrap(), "GNTMTT99C27H501");
+        assert_eq!(birth_key("GNTMTT99C67H501J").unwrap(), "GNTMTT99C27H501");
+        assert_eq!(birth_key("GNTMTT99C27HRLMD").unwrap(), "GNTMTT99C27H501");
+        assert_eq!(birth_key("GNTMTT99C41H501Q").unwrap(), "GNTMTT99C01H501");
+        assert!(birth_key("GNTMTT99C27H501K").is_err());
+        //spell-checker: enable
+    }
+
+    #[test]
+    fn test_birth_country_matches() {
+        //spell-checker: disable
+        assert!(birth_country_matches("GNTMTT99C27H501F", "IT").unwrap());
+        assert!(birth_country_matches("GNTMTT99C27HRLMD", "it").unwrap());
+        assert!(birth_country_matches("FCKTSS05C01Z130C", "sm").unwrap());
+        assert!(!birth_country_matches("FCKTSS05C01Z130C", "IT").unwrap());
+        assert!(birth_country_matches("GNTMTT99C27H501K", "IT").is_err());
+        //spell-checker: enable
+    }
+
+    #[test]
+    fn test_is_at_least() {
+        let date = |y, m, d| NaiveDate::from_ymd_opt(y, m, d).unwrap();
+
+        //spell-checker: disable
+        assert!(!is_at_least("GNTMTT99C27H501F", 18, date(2017, 3, 26)).unwrap());
+        assert!(is_at_least("GNTMTT99C27H501F", 18, date(2017, 3, 27)).unwrap());
+        assert!(is_at_least("GNTMTT99C27H501F", 18, date(2026, 1, 1)).unwrap());
+
+        // near the century edges
+        assert!(!is_at_least("GNTMTT99C27H501F", 1, date(2000, 1, 1)).unwrap());
+        assert!(is_at_least("GNTMTT99C27H501F", 18, date(2050, 1, 1)).unwrap());
+        assert!(is_at_least("GNTMTT99C27H501F", 60, date(2060, 6, 1)).unwrap());
+        assert!(!is_at_least("GNTMTT99C27H501F", 62, date(2060, 6, 1)).unwrap());
+        assert!(is_at_least("GNTMTT49C27H501T", 99, date(2049, 3, 26)).unwrap());
+        assert!(!is_at_least("GNTMTT49C27H501T", 100, date(2049, 3, 26)).unwrap());
+        // on the 100th birthday the code is read as a birth on that day
+        assert!(!is_at_least("GNTMTT49C27H501T", 18, date(2049, 3, 27)).unwrap());
+
+        // born on the 29th of February 2004
+        let code = "RSSMRA04B29H501C";
+        assert!(!is_at_least(code, 18, date(2022, 2, 28)).unwrap());
+        assert!(is_at_least(code, 18, date(2022, 3, 1)).unwrap());
+
+        assert!(is_at_least("GNTMTT99C27H501K", 18, date(2026, 1, 1)).is_err());
+        //spell-checker: enable
+    }
+
+    #[test]
+    fn test_format_grouped() {
+        //spell-checker: disable
+        assert_eq!(
+            format_grouped("gntmtt99c27h501f").unwrap(),
+            "GNT MTT 99C27 H501 F"
+        );
+        assert_eq!(
+            format_grouped("GNTMTT99C27HRLMD").unwrap(),
+            "GNT MTT 99C27 HRLM D"
+        );
+        assert_eq!(
+            format_grouped("GNTMTT99C27H501K"),
+            Err(FiscalCodeError::InvalidCheckCharacter {
+                found: 'K',
+                expected: 'F'
+            })
+        );
+        //spell-checker: enable
+    }
+
+    #[test]
+    fn test_placeholder() {
+        assert!(is_placeholder(PLACEHOLDER));
+        assert!(is_placeholder(" xxxxxx00x00x000o "));
+        assert_eq!(
+            validate_or_error(PLACEHOLDER),
+            Err(FiscalCodeError::InvalidBirthDay { encoded: 0 })
+        );
+        //spell-checker: disable
+        assert!(!is_placeholder("GNTMTT99C27H501F"));
+        //spell-checker: enable
+    }
+
+    #[test]
+    fn test_mask() {
+        //spell-checker: disable
+        assert_eq!(mask(" gntmtt99c27h501f "), "GNTMTT*********F");
+        assert_eq!(mask("GNTMTT99C27H50MX"), "GNTMTT*********X");
+        assert_eq!(mask("GNTMTT99"), "GNTMTT**");
+        assert_eq!(mask_with("GNTMTT99C27H501F", &[]), "****************");
+        assert_eq!(
+            mask_with("GNTMTT99C27H501F", &[11, 12, 13, 14]),
+            "***********H501*"
+        );
+        assert_eq!(mask("ßNTMTT99C27H501F"), "ßNTMTT*********F");
+        //spell-checker: enable
+    }
+
+    #[test]
+    fn test_plausible_name_block() {
+        //spell-checker: disable
+        assert!(plausible_name_block("GNTMTT99C27H501F"));
+        assert!(plausible_name_block("FOXLAX05C01Z122A"));
+        assert!(plausible_name_block("MXXVEA"));
+        assert!(!plausible_name_block("XXXMTT99C27H501F"));
+        assert!(!plausible_name_block("GNTAMT99C27H501F"));
+        assert!(!plausible_name_block("GAXAMT99C27H501F"));
+        assert!(!plausible_name_block("GNT0TT99C27H501F"));
+        //spell-checker: enable
+        assert!(!plausible_name_block("ABC"));
+        assert!(!plausible_name_block("ABéAAA"));
+        assert!(!plausible_name_block("AAéBBBxxxxxxxxxx"));
+    }
+
+    #[test]
+    fn test_verify_against() {
+        //spell-checker: disable
+        assert!(verify_against("GNTMTT99C27H501F", "GNTMTT99C27H501F"));
+        assert!(verify_against(" gntmtt99c27h501f", "GNTMTT99C27H501F "));
+        assert!(!verify_against("GNTMTT99C27H501F", "GNTMTT99C27H501K"));
+        assert!(!verify_against("HNTMTT99C27H501F", "GNTMTT99C27H501F"));
+        assert!(!verify_against("GNTMTT99C27HRLMD", "GNTMTT99C27H501F"));
+        assert!(!verify_against("GNTMTT99C27H501", "GNTMTT99C27H501F"));
+        //spell-checker: enable
+        assert!(verify_against("", ""));
+    }
+
+    #[test]
+    fn test_confidence() {
+        //spell-checker: disable
+        assert_eq!(confidence("GNTMTT99C27H501F"), Confidence::High);
+        assert_eq!(confidence("GNTMTT99C27HRLMD"), Confidence::High);
+        assert_eq!(confidence("GNTMTT99C27A000N"), Confidence::Medium);
+        assert_eq!(confidence("GNTAMT99C27H501X"), Confidence::Medium);
+        assert_eq!(confidence("GNTMTT99C27H501K"), Confidence::Low);
+        assert_eq!(confidence("FCKTSS05B30Z122E"), Confidence::Low);
+        //spell-checker: enable
+        assert_eq!(confidence("12345678903"), Confidence::Low);
+        assert!(Confidence::Low < Confidence::High);
+    }
+
+    #[test]
+    fn test_classification_tables() {
+        for (&digit, &letter) in DIGIT_REPLACEMENTS.entries() {
+            assert_eq!(DIGIT_VALUES[letter as usize], Some(digit));
+            assert_eq!(DIGIT_VALUES[(b'0' + digit) as usize], Some(digit));
+        }
+        assert_eq!(DIGIT_VALUES.iter().flatten().count(), 20);
+        assert_eq!(UPPERCASE.iter().filter(|&&upper| upper).count(), 26);
+        for (i, &class) in POSITION_CLASSES.iter().enumerate() {
+            assert_eq!(class == CharClass::Digit, OMOCODIA_INDICES.contains(&i));
+        }
+
+        assert_eq!(classify_position(0, b'A'), Ok(b'A'));
+        assert_eq!(
+            classify_position(0, b'1'),
+            Err(PositionKind::ExpectedLetter)
+        );
+        assert_eq!(classify_position(6, b'V'), Ok(b'9'));
+        assert_eq!(classify_position(6, b'A'), Err(PositionKind::ExpectedDigit));
+        assert_eq!(
+            classify_position(6, b'-'),
+            Err(PositionKind::ExpectedLetterOrDigit)
+        );
+    }
+
+    #[test]
+    fn test_check_char_tables() {
+        let odd: Vec<_> = check_char_odd_table().collect();
+        let even: Vec<_> = check_char_even_table().collect();
+        assert_eq!(odd.len(), 36);
+        assert_eq!(even.len(), 36);
+        assert_eq!(odd[..3], [('0', 1), ('1', 0), ('2', 5)]);
+        assert!(odd.contains(&('Z', 23)));
+        assert!(even.contains(&('A', 0)));
+        assert!(even.contains(&('9', 9)));
+    }
+
+    #[test]
+    fn test_check_character_breakdown() {
+        //spell-checker: disable
+        let (contributions, reminder, check_character) =
+            check_character_breakdown("GNTMTT99C27H501").unwrap();
+        assert_eq!(
+            contributions,
+            [15, 13, 14, 12, 14, 19, 21, 9, 5, 2, 17, 7, 13, 0, 0]
+        );
+        assert_eq!(reminder, 5);
+        assert_eq!(check_character, 'F');
+
+        assert_eq!(
+            check_character_breakdown("GNTMTT99C27H501F"),
+            Err(FiscalCodeError::InvalidLength)
+        );
+        assert_eq!(
+            check_character_breakdown("GNTMTT99C27H50-"),
+            Err(FiscalCodeError::InvalidCharacter {
+                position: 14,
+                expected: PositionKind::ExpectedLetterOrDigit
+            })
+        );
+        //spell-checker: enable
+    }
+
+    #[test]
+    fn test_check_character_sum() {
+        //spell-checker: disable
+        assert_eq!(check_character_sum("GNTMTT99C27H501"), Ok(161));
+        assert_eq!(
+            check_character_sum("GNTMTT99C27HRLM").map(|sum| sum % 26),
+            Ok(3)
+        );
+        assert_eq!(
+            check_character_sum("GNTMTT99C27H501F"),
+            Err(FiscalCodeError::InvalidLength)
+        );
+        //spell-checker: enable
+    }
+
+    #[test]
+    fn test_check_character_large_sum() {
+        // the sum of the values exceeds u8::MAX
+        //spell-checker: disable
+        let (contributions, _, check_character) =
+            check_character_breakdown("ZZZZZZ99Z99Z999").unwrap();
+        assert!(contributions.iter().map(|&v| u32::from(v)).sum::<u32>() > 255);
+        assert_eq!(
+            calculate_check_character("ZZZZZZ99Z99Z999"),
+            check_character
+        );
+        //spell-checker: enable
+    }
+
+    #[test]
+    fn test_position_errors() {
+        //spell-checker: disable
+        assert!(position_errors("GNTMTT99C27H501F").is_empty());
+        assert!(position_errors("GNTMTT99C27HR0MS").is_empty());
+        assert_eq!(
+            position_errors("GNT0TT99C27H5A1F"),
+            vec![
+                (3, PositionKind::ExpectedLetter),
+                (13, PositionKind::ExpectedDigit)
+            ]
+        );
+        assert_eq!(
+            position_errors("GNTMTT9-327H501-"),
+            vec![
+                (7, PositionKind::ExpectedLetterOrDigit),
+                (8, PositionKind::ExpectedLetter),
+                (15, PositionKind::ExpectedLetter),
+            ]
+        );
+        assert!(position_errors("gntmtt99c27h501f").is_empty());
+        assert_eq!(
+            position_errors("ßNTMTT99C27H501F"),
+            vec![(0, PositionKind::ExpectedLetter)]
+        );
+        //spell-checker: enable
+        assert!(position_errors("").is_empty());
+    }
+
+    #[test]
+    fn test_fiscal_code_str() {
+        //spell-checker: disable
+        let code = FiscalCodeStr::try_from(" gntmtt99c27h50mx ").unwrap();
+        assert_eq!(code.as_str(), "GNTMTT99C27H50MX");
+        assert_eq!(code.to_string(), "GNTMTT99C27H50MX");
+        assert_eq!(&code[..6], "GNTMTT");
+        assert_eq!(String::from(code), "GNTMTT99C27H50MX");
+        assert_eq!(code, "GNTMTT99C27H50MX".parse().unwrap());
+        assert_eq!(code.lowercase().to_string(), "gntmtt99c27h50mx");
+        assert_eq!(
+            FiscalCodeStr::try_from(code.lowercase().to_string()).unwrap(),
+            code
+        );
+
+        assert!(FiscalCodeStr::try_from("FCKTSS05C01Z122K").is_err());
+        assert!(FiscalCodeStr::try_from("12345678903").is_err());
+        //spell-checker: enable
+    }
+
+    #[test]
+    fn test_parse() {
+        //spell-checker: disable
+        let code = parse(" gntmtt99c27h50mx").unwrap();
+        assert_eq!(code.representation(), "GNTMTT99C27H50MX");
+        assert_eq!(code.representation_canonical(), "GNTMTT99C27H501F");
+        assert_eq!(code.surname(), "GNT");
+        assert_eq!(code.name(), "MTT");
+        assert_eq!(
+            code.born_on(),
+            NaiveDate::from_ymd_opt(1999, 3, 27).unwrap()
+        );
+        assert_eq!(code.gender(), &Gender::Male);
+        assert_eq!(code.place_of_birth().city, Some("Roma".into()));
+        assert!(code.is_omocodia());
+        assert!(!parse("GNTMTT99C27H501F").unwrap().is_omocodia());
+
+        assert!(parse("12345678903").is_err());
+        //spell-checker: enable
+    }
+
+    #[test]
+    fn test_verify() {
+        //spell-checker: disable
+        match verify("GNTMTT99C27HRLMD") {
+            VerifyResult::Valid(info) => assert_eq!(info.place_of_birth.city, Some("Roma".into())),
+            result => panic!("unexpected {:?}", result),
+        }
+        //spell-checker: enable
+        match verify(" 12345678903 ") {
+            VerifyResult::ValidTemporary(info) => assert_eq!(
+                info,
+                TemporaryInfo {
+                    code: "12345678903".into(),
+                    kind: NumericCodeKind::Temporary
+                }
+            ),
+            result => panic!("unexpected {:?}", result),
+        }
+        assert!(matches!(
+            verify("12345678904"),
+            VerifyResult::Invalid(FiscalCodeError::InvalidTemporaryCheckCharacter)
+        ));
+        assert!(matches!(
+            verify("TOOSHORT"),
+            VerifyResult::Invalid(FiscalCodeError::InvalidLength)
+        ));
+    }
+
+    #[test]
+    fn test_fixed_width_column() {
+        //spell-checker: disable
+        assert_eq!(validate_or_error("GNTMTT99C27H501F   "), Ok(()));
+        assert_eq!(
+            parse("GNTMTT99C27H501F    ").unwrap().representation(),
+            "GNTMTT99C27H501F"
+        );
+        //spell-checker: enable
+        assert_eq!(
+            validate_or_error(&" ".repeat(16)),
+            Err(FiscalCodeError::EmptyInput)
+        );
+        assert_eq!(parse("").unwrap_err(), FiscalCodeError::EmptyInput);
+        assert_eq!(
+            validate_or_error("TOOSHORT   "),
+            Err(FiscalCodeError::InvalidLength)
+        );
+    }
+
+    #[test]
+    fn test_classify() {
+        //spell-checker: disable
+        assert_eq!(classify("GNTMTT99C27H501F"), FiscalCodeKind::Personal);
+        assert_eq!(
+            classify("GNTMTT99C27HRLMD"),
+            FiscalCodeKind::PersonalOmocodia
+        );
+        assert_eq!(classify("GNTMTT99C27H501K"), FiscalCodeKind::Invalid);
+        //spell-checker: enable
+        assert_eq!(classify("00743110157"), FiscalCodeKind::TemporaryOrVat);
+        assert_eq!(classify("12345678903"), FiscalCodeKind::TemporaryOrVat);
+        assert_eq!(classify("12345678904"), FiscalCodeKind::Invalid);
+        assert_eq!(classify("STP1201010000001"), FiscalCodeKind::StpEni);
+    }
+
+    #[test]
+    fn test_parse_any() {
+        //spell-checker: disable
+        match parse_any("GNTMTT99C27HRLMD") {
+            Ok(ParsedCode::Personal(code)) => {
+                assert_eq!(code.representation_canonical, "GNTMTT99C27H501F")
+            }
+            result => panic!("unexpected {:?}", result),
+        }
+        //spell-checker: enable
+        match parse_any(" 12345678903 ") {
+            Ok(ParsedCode::Temporary(info)) => {
+                assert_eq!(info.code, "12345678903");
+                assert_eq!(info.kind, NumericCodeKind::Temporary);
+            }
+            result => panic!("unexpected {:?}", result),
+        }
+        assert_eq!(
+            parse_any("12345678904").unwrap_err(),
+            FiscalCodeError::InvalidTemporaryCheckCharacter
+        );
+        assert_eq!(
+            parse_any("TOOSHORT").unwrap_err(),
+            FiscalCodeError::InvalidLength
+        );
+    }
+
+    #[test]
+    fn test_info_raw() {
+        //spell-checker: disable
+        let info = info_raw("GNTMTT99C27H501F").unwrap();
+        assert_eq!(info.place_of_birth.city, Some("Roma".into()));
+
+        assert!(super::info("GNTMTT99C27HRLMD").is_ok());
+        assert_eq!(
+            info_raw("GNTMTT99C27HRLMD").unwrap_err(),
+            FiscalCodeError::InvalidCharacter {
+                position: 12,
+                expected: PositionKind::ExpectedDigit
+            }
+        );
+        assert_eq!(
+            info_raw("GNTMTT99C27H501").unwrap_err(),
+            FiscalCodeError::InvalidLength
+        );
+        //spell-checker: enable
+    }
+
+    #[test]
+    fn test_info_with_clock() {
+        let today = NaiveDate::from_ymd_opt(2010, 6, 1).unwrap();
+        //spell-checker: disable
+        let info = info_with_clock("FCKTSS05C01Z122F", &today).unwrap();
+        assert_eq!(info.born_on, NaiveDate::from_ymd_opt(2005, 3, 1).unwrap());
+
+        let today = NaiveDate::from_ymd_opt(2004, 6, 1).unwrap();
+        let info = info_with_clock("FCKTSS05C01Z122F", &today).unwrap();
+        //spell-checker: enable
+        assert_eq!(info.born_on, NaiveDate::from_ymd_opt(1905, 3, 1).unwrap());
+    }
+
+    #[test]
+    fn test_info_with_clock_centuries() {
+        let date = |y, m, d| NaiveDate::from_ymd_opt(y, m, d).unwrap();
+        let born_on = |code, today: NaiveDate| info_with_clock(code, &today).unwrap().born_on;
+        //spell-checker: disable
+        assert_eq!(
+            born_on("GNTMTT99C27H501F", date(1990, 6, 1)),
+            date(1899, 3, 27)
+        );
+        for year in [2049, 2050, 2060] {
+            assert_eq!(
+                born_on("GNTMTT99C27H501F", date(year, 6, 1)),
+                date(1999, 3, 27)
+            );
+        }
+        assert_eq!(
+            born_on("GNTMTT49C27H501T", date(2049, 6, 1)),
+            date(2049, 3, 27)
+        );
+        assert_eq!(
+            born_on("GNTMTT49C27H501T", date(2049, 3, 26)),
+            date(1949, 3, 27)
+        );
+        // born today
+        assert_eq!(
+            born_on("GNTMTT10D14H501U", date(2010, 4, 14)),
+            date(2010, 4, 14)
+        );
+        assert_eq!(
+            born_on("GNTMTT10D14H501U", date(2010, 4, 13)),
+            date(1910, 4, 14)
+        );
+        //spell-checker: enable
+    }
+
+    #[test]
+    fn test_info_with_placeholder_towns() {
+        //spell-checker: disable
+        assert_eq!(
+            super::info("GNTMTT99C27XXXXD").unwrap_err(),
+            FiscalCodeError::InvalidCharacter {
+                position: 12,
+                expected: PositionKind::ExpectedDigit
+            }
+        );
+        let info = info_with_placeholder_towns(" gntmtt99c27xxxxd", &["XXXX"]).unwrap();
+        assert!(info.place_of_birth.is_placeholder);
+        assert_eq!(info.place_of_birth.country_code, "");
+        assert_eq!(info.born_on, NaiveDate::from_ymd_opt(1999, 3, 27).unwrap());
+
+        let info = info_with_placeholder_towns("GNTMTT99C27A000N", &["A000"]).unwrap();
+        assert!(info.place_of_birth.is_placeholder);
+        let info = info_with_placeholder_towns("GNTMTT99C27H501F", &["XXXX"]).unwrap();
+        assert!(!info.place_of_birth.is_placeholder);
+
+        assert_eq!(
+            info_with_placeholder_towns("GNTMTT99C27XXXXK", &["XXXX"]).unwrap_err(),
+            FiscalCodeError::InvalidCheckCharacter {
+                found: 'K',
+                expected: 'D'
+            }
+        );
+        //spell-checker: enable
+    }
+
+    #[test]
+    fn test_info_with_extra_towns() {
+        //spell-checker: disable
+        let new_town = Location {
+            country_code: "IT",
+            country_name: "Italia",
+            city: Some("Nuovo Comune"),
+            state: Some("RM"),
+            region: None,
+            continent: None,
+            valid_from: None,
+            valid_to: None,
         };
+        let extra_towns = HashMap::from([("A000".to_string(), new_town)]);
 
-        if let Some(captures) = regex.captures(&code_canonical) {
-            let birth_year = captures.get(3).unwrap().as_str().parse().unwrap();
-            let birth_month = captures.get(4).unwrap().as_str().chars().next().unwrap();
-            let birth_day_gender = captures.get(5).unwrap().as_str().parse().unwrap();
-            let birth_town = captures.get(6).unwrap().as_str();
-            let check_character_actual = captures.get(7).unwrap().as_str().chars().next().unwrap();
-
-            if check_character_actual != check_character_calculated {
-                return Err(format!(
-                    "Invalid check character: found {}, expected {}",
-                    check_character_actual, check_character_calculated,
-                )
-                .into());
-            }
+        assert_eq!(
+            super::info("RSSMRA85T10A000W").unwrap_err(),
+            FiscalCodeError::InvalidBirthTown
+        );
+        let info = info_with_extra_towns("RSSMRA85T10A000W", &extra_towns).unwrap();
+        assert_eq!(info.place_of_birth.city, Some("Nuovo Comune".into()));
 
-            Ok(FiscalCode {
-                representation: code,
-                representation_canonical: captures.get(0).unwrap().as_str().into(),
-                surname: captures.get(1).unwrap().as_str().into(),
-                name: captures.get(2).unwrap().as_str().into(),
-                born_on: born_on(birth_year, birth_month, birth_day_gender)?,
-                gender: gender(birth_day_gender),
-                place_of_birth: place_of_birth(birth_town)?,
-            })
-        } else {
-            Err("Invalid fiscal code format".into())
-        }
+        // the extra towns take precedence over the dataset
+        let extra_towns = HashMap::from([("H501".to_string(), new_town)]);
+        let info = info_with_extra_towns("RSSMRA85T10H501O", &extra_towns).unwrap();
+        assert_eq!(info.place_of_birth.city, Some("Nuovo Comune".into()));
+        //spell-checker: enable
     }
-}
 
-fn born_on(
-    birth_year: u8,
-    birth_month: char,
-    birth_day_gender: u8,
-) -> Result<NaiveDate, Box<dyn Error>> {
-    let day = if birth_day_gender > 40 {
-        birth_day_gender - 40
-    } else {
-        birth_day_gender
-    };
+    #[test]
+    fn test_info_with_notes() {
+        //spell-checker: disable
+        let (info, notes) = info_with_notes("GNTMTT99C27H501F").unwrap();
+        assert_eq!(info.born_on, NaiveDate::from_ymd_opt(1999, 3, 27).unwrap());
+        assert_eq!(notes, vec![GenerationNote::CenturyAssumed]);
 
-    let month = *BIRTH_MONTHS
-        .entries()
-        .find(|(_, &c)| c == birth_month)
-        .ok_or("Invalid birth month")?
-        .0
-        + 1;
+        let (_, notes) = info_with_notes("FOXLAX05C01Z122A").unwrap();
+        //spell-checker: enable
+        assert_eq!(
+            notes,
+            vec![
+                GenerationNote::SurnamePadded,
+                GenerationNote::NamePadded,
+                GenerationNote::CenturyAssumed
+            ]
+        );
+    }
 
-    let year = {
-        let current = Utc::now().year() as f32;
+    #[test]
+    fn test_residency_hint() {
+        //spell-checker: disable
+        let info = super::info("GNTMTT99C27H501F").unwrap();
+        assert_eq!(info.residency_hint(), ResidencyHint::BornInItaly);
+        assert_eq!(
+            info.residency_hint_for_citizen(false),
+            ResidencyHint::BornInItaly
+        );
 
-        let year = ((current / 100.0).round() * 100.0) as i32 + birth_year as i32;
+        let info = super::info("MKSKRS92L65Z219S").unwrap();
+        //spell-checker: enable
+        assert_eq!(info.residency_hint(), ResidencyHint::Foreigner);
+        assert_eq!(
+            info.residency_hint_for_citizen(true),
+            ResidencyHint::BornAbroadItalian
+        );
+    }
 
-        if year < current as i32 {
-            year
-        } else {
-            year - 100
-        }
-    };
+    #[test]
+    fn test_dataset_stats() {
+        let stats = dataset_stats();
+        assert_eq!(stats.towns, BIRTH_TOWNS.len());
+        assert_eq!(stats.italian + stats.foreign, stats.towns);
+        assert_eq!(
+            stats.italian,
+            BIRTH_TOWNS
+                .values()
+                .filter(|location| location.country_code == "IT")
+                .count()
+        );
+        assert!(stats.foreign > 0);
+    }
 
-    Ok(NaiveDate::from_ymd_opt(year, month.into(), day.into()).ok_or("Invalid birth date")?)
-}
+    #[test]
+    fn test_lookup_town() {
+        let location = lookup_town("H501").unwrap();
+        assert_eq!(location.country_code, "IT");
+        assert_eq!(location.city, Some("Roma"));
+        assert_eq!(location.state, Some("RM"));
+        assert_eq!(PlaceOfBirth::from(location).city, Some("Roma".into()));
 
-fn gender(birth_day_gender: u8) -> Gender {
-    if birth_day_gender > 40 {
-        Gender::Female
-    } else {
-        Gender::Male
+        assert_eq!(lookup_town("Z219").unwrap().country_name, "Giappone");
+        assert!(lookup_town("A000").is_none());
+        assert!(lookup_town("h501").is_none());
     }
-}
 
-fn place_of_birth(birth_town: &str) -> Result<PlaceOfBirth, Box<dyn Error>> {
-    let location = *BIRTH_TOWNS.get(birth_town).ok_or("Invalid birth town")?;
+    #[test]
+    fn test_countries() {
+        let countries = countries();
+        assert!(countries.contains(&("IT", "Italia")));
+        assert!(countries.contains(&("SM", "San Marino")));
+        assert!(countries.windows(2).all(|pair| pair[0].1 <= pair[1].1));
 
-    Ok(PlaceOfBirth {
-        country_code: location.country_code.into(),
-        country_name: location.country_name.into(),
-        city: location.city.map(|v| v.into()),
-        state: location.state.map(|v| v.into()),
-    })
-}
+        let mut codes: Vec<&str> = countries.iter().map(|&(code, _)| code).collect();
+        codes.sort_unstable();
+        codes.dedup();
+        assert_eq!(codes.len(), countries.len());
+    }
 
-static BIRTH_MONTHS: phf::OrderedMap<u8, char> = phf_ordered_map! {
-    0u8 => 'A',
-    1u8 => 'B',
-    2u8 => 'C',
-    3u8 => 'D',
-    4u8 => 'E',
-    5u8 => 'H',
-    6u8 => 'L',
-    7u8 => 'M',
-    8u8 => 'P',
-    9u8 => 'R',
-    10u8 => 'S',
-    11u8 => 'T',
-};
+    #[test]
+    fn test_belfiore_codes_in_region() {
+        let codes = belfiore_codes_in_region("Lombardia");
+        assert!(codes.contains(&"F205"));
+        assert!(!codes.contains(&"H501"));
+        assert!(codes.windows(2).all(|pair| pair[0] < pair[1]));
+        assert_eq!(belfiore_codes_in_region(" lombardia "), codes);
 
-static DIGIT_REPLACEMENTS: phf::OrderedMap<u8, char> = phf_ordered_map! {
-   0u8 => 'L',
-   1u8 => 'M',
-   2u8 => 'N',
-   3u8 => 'P',
-   4u8 => 'Q',
-   5u8 => 'R',
-   6u8 => 'S',
-   7u8 => 'T',
-   8u8 => 'U',
-   9u8 => 'V',
-};
+        assert!(belfiore_codes_in_region("Valle d'Aosta").contains(&"A326"));
+        assert!(belfiore_codes_in_region("Atlantide").is_empty());
+    }
 
-static CHECK_CHARACTER_ODD_REPLACEMENTS: phf::OrderedMap<char, u8> = phf_ordered_map! {
-   '0' => 1u8,
-   '1' => 0u8,
-   '2' => 5u8,
-   '3' => 7u8,
-   '4' => 9u8,
-   '5' => 13u8,
-   '6' => 15u8,
-   '7' => 17u8,
-   '8' => 19u8,
-   '9' => 21u8,
-   'A' => 1u8,
-   'B' => 0u8,
-   'C' => 5u8,
-   'D' => 7u8,
-   'E' => 9u8,
-   'F' => 13u8,
-   'G' => 15u8,
-   'H' => 17u8,
-   'I' => 19u8,
-   'J' => 21u8,
-   'K' => 2u8,
-   'L' => 4u8,
-   'M' => 18u8,
-   'N' => 20u8,
-   'O' => 11u8,
-   'P' => 3u8,
-   'Q' => 6u8,
-   'R' => 8u8,
-   'S' => 12u8,
-   'T' => 14u8,
-   'U' => 16u8,
-   'V' => 10u8,
-   'W' => 22u8,
-   'X' => 25u8,
-   'Y' => 24u8,
-   'Z' => 23u8,
-};
+    #[test]
+    fn test_suggest_belfiore() {
+        let suggestions = suggest_belfiore("h50l");
+        assert!(suggestions.contains(&"H501"), "{:?}", suggestions);
+        assert!(suggestions.len() <= MAX_BELFIORE_SUGGESTIONS);
+        assert!(suggestions.iter().all(|code| lookup_town(code).is_some()));
 
-static CHECK_CHARACTER_EVEN_REPLACEMENTS: phf::OrderedMap<char, u8> = phf_ordered_map! {
-   '0' => 0u8,
-   '1' => 1u8,
-   '2' => 2u8,
-   '3' => 3u8,
-   '4' => 4u8,
-   '5' => 5u8,
-   '6' => 6u8,
-   '7' => 7u8,
-   '8' => 8u8,
-   '9' => 9u8,
-   'A' => 0u8,
-   'B' => 1u8,
-   'C' => 2u8,
-   'D' => 3u8,
-   'E' => 4u8,
-   'F' => 5u8,
-   'G' => 6u8,
-   'H' => 7u8,
-   'I' => 8u8,
-   'J' => 9u8,
-   'K' => 10u8,
-   'L' => 11u8,
-   'M' => 12u8,
-   'N' => 13u8,
-   'O' => 14u8,
-   'P' => 15u8,
-   'Q' => 16u8,
-   'R' => 17u8,
-   'S' => 18u8,
-   'T' => 19u8,
-   'U' => 20u8,
-   'V' => 21u8,
-   'W' => 22u8,
-   'X' => 23u8,
-   'Y' => 24u8,
-   'Z' => 25u8,
-};
+        assert!(!suggest_belfiore("H501").contains(&"H501"));
+        // a missing or an extra character
+        assert!(suggest_belfiore("H51").contains(&"H501"));
+        assert!(suggest_belfiore("h5011").contains(&"H501"));
+        assert!(suggest_belfiore("H5").is_empty());
+        assert!(suggest_belfiore("99QQ").is_empty());
+        // not uppercased to `FF01`
+        assert!(suggest_belfiore("ﬀ01").is_empty());
+    }
 
-static CHECK_CHARACTER_REMINDER: phf::OrderedMap<u8, char> = phf_ordered_map! {
-   0u8 => 'A',
-   1u8 => 'B',
-   2u8 => 'C',
-   3u8 => 'D',
-   4u8 => 'E',
-   5u8 => 'F',
-   6u8 => 'G',
-   7u8 => 'H',
-   8u8 => 'I',
-   9u8 => 'J',
-   10u8 => 'K',
-   11u8 => 'L',
-   12u8 => 'M',
-   13u8 => 'N',
-   14u8 => 'O',
-   15u8 => 'P',
-   16u8 => 'Q',
-   17u8 => 'R',
-   18u8 => 'S',
-   19u8 => 'T',
-   20u8 => 'U',
-   21u8 => 'V',
-   22u8 => 'W',
-   23u8 => 'X',
-   24u8 => 'Y',
-   25u8 => 'Z',
-};
+    #[test]
+    fn test_one_edit_apart() {
+        let chars = |s: &str| s.chars().collect::<Vec<_>>();
+        assert!(one_edit_apart(&chars("H501"), &chars("H50L")));
+        assert!(one_edit_apart(&chars("H501"), &chars("H51")));
+        assert!(one_edit_apart(&chars("H501"), &chars("H5011")));
+        assert!(one_edit_apart(&chars("H501"), &chars("XH501")));
+        assert!(!one_edit_apart(&chars("H501"), &chars("H501")));
+        assert!(!one_edit_apart(&chars("H501"), &chars("H5")));
+        assert!(!one_edit_apart(&chars("H501"), &chars("H015")));
+    }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    #[test]
+    fn test_all_locations_for() {
+        assert_eq!(
+            all_locations_for("H501"),
+            vec![lookup_town("H501").unwrap()]
+        );
+        assert!(all_locations_for("A000").is_empty());
+    }
 
     #[test]
-    fn test_validate() {
+    fn test_gender_as_u8() {
+        for gender in [Gender::Female, Gender::Male] {
+            assert_eq!(Gender::from_u8(gender.as_u8()), Some(gender));
+        }
+        assert_eq!(Gender::Female.as_u8(), 0);
+        assert_eq!(Gender::Male.as_u8(), 1);
+        assert_eq!(Gender::from_u8(2), None);
+    }
+
+    #[test]
+    fn test_info_to_fields() {
         //spell-checker: disable
-        assert!(validate("GNTMTT99C27H501F"));
-        assert!(validate("MRARSS80A01H501T"));
-        assert!(validate("BNCLRD69T61A783M"));
-        assert!(validate("FCKTSS05C01Z122F"));
-        assert!(validate("FCKTSS05C01ZMLQH"));
+        let fields = super::info("GNTMTT99C27H501F").unwrap().to_fields();
+        assert_eq!(
+            fields,
+            [
+                ("born_on", "1999-03-27"),
+                ("gender", "M"),
+                ("country", "Italia"),
+                ("country_code", "IT"),
+                ("city", "Roma"),
+                ("state", "RM"),
+                ("year_is_ambiguous", "true"),
+            ]
+            .map(|(key, value)| (key, value.to_string()))
+        );
 
-        assert!(!validate("INVALIDCODE"));
-        assert!(!validate("FCKTSS05C01Z122K"));
-        assert!(!validate("FCKTSS05F01Z122F"));
-        assert!(!validate("FCKTSS05C32Z122F"));
-        assert!(!validate("FCKTSS05C01Z105L"));
-        assert!(!validate("GNTMTT99C72H501Y"));
+        let fields = super::info("FCKTSS05C01Z130C").unwrap().to_fields();
+        assert_eq!(fields[4], ("city", "".to_string()));
         //spell-checker: enable
     }
 
     #[test]
-    fn test_validate_omocodia() {
+    fn test_info_display() {
         //spell-checker: disable
-        assert!(validate("GNTMTT99C27H50MX"));
-        assert!(validate("GNTMTT99C27HR0MS"));
+        let info = super::info("RSSMRA85T10H501O").unwrap();
         //spell-checker: enable
+        assert_eq!(
+            info.to_string(),
+            "\tBorn on: 1985-12-10\n\tGender: M\n\tCountry: Italia (IT)\n\tCity: Roma (RM)"
+        );
     }
 
     #[test]
-    fn test_validate_provisional() {
-        assert!(validate("12345678903"));
+    fn test_location_in() {
+        //spell-checker: disable
+        let old = Location {
+            country_code: "IT",
+            country_name: "Italia",
+            city: Some("Vecchio Comune"),
+            state: Some("RM"),
+            region: None,
+            continent: None,
+            valid_from: None,
+            valid_to: Some(1927),
+        };
+        let new = Location {
+            city: Some("Nuovo Comune"),
+            valid_from: Some(1928),
+            valid_to: None,
+            ..old
+        };
+        //spell-checker: enable
+        assert_eq!(location_in(&[&old, &new], 1900), Some(&old));
+        assert_eq!(location_in(&[&old, &new], 1927), Some(&old));
+        assert_eq!(location_in(&[&old, &new], 1928), Some(&new));
+        assert_eq!(location_in(&[&old], 1990), None);
     }
 
     #[test]
-    fn test_validate_empty() {
-        assert!(!validate(""));
+    fn test_born_before_town() {
+        //spell-checker: disable
+        let town = Location {
+            country_code: "IT",
+            country_name: "Italia",
+            city: Some("Nuovo Comune"),
+            state: Some("RM"),
+            region: None,
+            continent: None,
+            valid_from: Some(1928),
+            valid_to: None,
+        };
+        //spell-checker: enable
+        assert!(born_before_town(&[&town], 1927));
+        assert!(!born_before_town(&[&town], 1928));
+        assert!(!born_before_town(&[], 1927));
+        let town = Location {
+            valid_from: None,
+            ..town
+        };
+        assert!(!born_before_town(&[&town], 1800));
     }
 
     #[test]
-    fn test_validate_invalid_length() {
-        // spell-checker: disable
-        assert!(!validate("TOOSHORT"));
-        assert!(!validate("THISCODEISTOOLONGTOBEAVALIDFISCALCODE"));
+    fn test_place_of_birth_constructors() {
+        //spell-checker: disable
+        let roma = PlaceOfBirth::from(lookup_town("H501").unwrap());
+        let place = PlaceOfBirth::italian("Roma", "RM");
+        assert_eq!(place.to_string(), roma.to_string());
+
+        let san_marino = PlaceOfBirth::from(lookup_town("Z130").unwrap());
+        let place = PlaceOfBirth::foreign("SM", "San Marino");
+        assert_eq!(place.to_string(), san_marino.to_string());
+        assert_eq!(place.continent, san_marino.continent);
+        assert!(place.is_microstate_birth());
+        //spell-checker: enable
+    }
+
+    #[test]
+    fn test_continent() {
+        //spell-checker: disable
+        let info = super::info("FCKTSS05C01Z130C").unwrap();
+        assert_eq!(info.place_of_birth.continent.as_deref(), Some("Europa"));
+
+        let place = PlaceOfBirth::from(lookup_town("Z404").unwrap());
+        assert_eq!(place.continent.as_deref(), Some("America"));
+        let place = PlaceOfBirth::from(lookup_town("Z907").unwrap());
+        assert_eq!(place.continent.as_deref(), Some("Africa"));
+
+        let info = super::info("RSSMRA85T10H501O").unwrap();
+        assert_eq!(info.place_of_birth.continent, None);
+        //spell-checker: enable
+    }
+
+    #[test]
+    fn test_info_microstate() {
+        //spell-checker: disable
+        let info = super::info("FCKTSS05C01Z130C").unwrap();
+        //spell-checker: enable
+        assert_eq!(info.place_of_birth.country_code, "SM");
+        assert_eq!(info.place_of_birth.country_name, "San Marino");
+        assert!(info.place_of_birth.is_microstate_birth());
+
+        //spell-checker: disable
+        let info = super::info("RSSMRA80A41Z106S").unwrap();
+        //spell-checker: enable
+        assert_eq!(info.place_of_birth.country_code, "VA");
+        assert!(info.place_of_birth.is_microstate_birth());
+
+        //spell-checker: disable
+        let info = super::info("GNTMTT99C27H501F").unwrap();
         //spell-checker: enable
+        assert!(!info.place_of_birth.is_microstate_birth());
     }
 
     #[test]
@@ -456,6 +2950,7 @@ mod tests {
             info.as_ref().unwrap().born_on,
             NaiveDate::from_ymd_opt(1999, 3, 27).unwrap()
         );
+        assert_eq!(info.as_ref().unwrap().birth_month(), Month::March);
         assert_eq!(info.as_ref().unwrap().gender, Gender::Male);
         assert_eq!(info.as_ref().unwrap().place_of_birth.country_name, "Italia");
         assert_eq!(info.as_ref().unwrap().place_of_birth.country_code, "IT");
@@ -476,6 +2971,7 @@ mod tests {
             info.as_ref().unwrap().born_on,
             NaiveDate::from_ymd_opt(1992, 7, 25).unwrap()
         );
+        assert_eq!(info.as_ref().unwrap().birth_month(), Month::July);
         assert_eq!(info.as_ref().unwrap().gender, Gender::Female);
         assert_eq!(
             info.as_ref().unwrap().place_of_birth.country_name,
@@ -484,5 +2980,13 @@ mod tests {
         assert_eq!(info.as_ref().unwrap().place_of_birth.country_code, "JP");
         assert!(info.as_ref().unwrap().place_of_birth.city.is_none());
         assert!(info.as_ref().unwrap().place_of_birth.state.is_none());
+        assert!(info.as_ref().unwrap().year_is_ambiguous);
+
+        // the 29th of February 1900 doesn't exist
+        //spell-checker: disable
+        let info = super::info("GNTMTT00B29H501B").unwrap();
+        //spell-checker: enable
+        assert_eq!(info.born_on, NaiveDate::from_ymd_opt(2000, 2, 29).unwrap());
+        assert!(!info.year_is_ambiguous);
     }
 }
@@ -1,6 +1,6 @@
 use std::io::{stdin, stdout, Write};
 
-use tommaso_fiscal_code::{info, validate_or_error};
+use tommaso_fiscal_code::{parse_any, ParsedCode};
 
 fn main() {
     loop {
@@ -13,17 +13,15 @@ fn main() {
             std::process::exit(1);
         });
 
-        let result = validate_or_error(&input);
-        match result {
-            Ok(_) => {
+        match parse_any(&input) {
+            Ok(ParsedCode::Personal(code)) => {
                 println!("Code is valid");
 
-                let info = info(&input).unwrap();
                 println!("Info:");
-                println!("\tBorn on: {}", info.born_on);
-                println!("\tGender: {}", info.gender);
-                println!("\t{}", info.place_of_birth);
+                println!("{}", code.info());
             }
+            // temporary codes can only be validated
+            Ok(_) => println!("Code is valid"),
             Err(e) => println!("Code is invalid: {}", e),
         }
         stdout().flush().unwrap();
@@ -0,0 +1,136 @@
+use crate::{calculate_check_character_temporary, FiscalCodeError, PositionKind};
+
+/// What an 11-digit code is, as far as its structure can tell.
+///
+/// Temporary fiscal codes and partita IVA numbers share the same layout and check digit,
+/// so most codes can't be told apart. See [classify_numeric].
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[non_exhaustive]
+pub enum NumericCodeKind {
+    /// The office code (digits 8 to 10) is not one used for partita IVA numbers,
+    /// so it can only be a temporary fiscal code.
+    Temporary,
+    /// Either a temporary fiscal code or a partita IVA.
+    Ambiguous,
+}
+
+/// What is known about a valid 11-digit code, see [crate::verify].
+#[derive(Debug, Clone, PartialEq)]
+pub struct TemporaryInfo {
+    /// The code, trimmed.
+    pub code: String,
+    pub kind: NumericCodeKind,
+}
+
+/// Validate an 11-digit code, telling its kind when the structure allows it.
+///
+/// Partita IVA numbers use the office codes `001..=100`, `120`, `121`, `888` and `999`:
+/// any other office code marks a temporary fiscal code.
+pub fn classify_numeric(code: &str) -> Result<NumericCodeKind, FiscalCodeError> {
+    let code = code.trim();
+    if code.is_empty() {
+        return Err(FiscalCodeError::EmptyInput);
+    }
+    if !code.is_ascii() {
+        return Err(FiscalCodeError::NonAscii);
+    }
+    if code.len() != 11 {
+        return Err(FiscalCodeError::InvalidLength);
+    }
+    if let Some(position) = code.find(|c: char| !c.is_ascii_digit()) {
+        return Err(FiscalCodeError::InvalidCharacter {
+            position,
+            expected: PositionKind::ExpectedDigit,
+        });
+    }
+
+    let (code, check_character) = code.split_at(10);
+    if check_character != calculate_check_character_temporary(code).to_string() {
+        return Err(FiscalCodeError::InvalidTemporaryCheckCharacter);
+    }
+
+    let office = office_code(code);
+    if matches!(office, 1..=100 | 120 | 121 | 888 | 999) {
+        Ok(NumericCodeKind::Ambiguous)
+    } else {
+        Ok(NumericCodeKind::Temporary)
+    }
+}
+
+/// The code of the office (Ufficio dell'Agenzia delle Entrate) that issued a valid
+/// 11-digit code, i.e. its digits 8 to 10.
+pub fn temporary_office_code(code: &str) -> Result<u16, FiscalCodeError> {
+    classify_numeric(code)?;
+
+    Ok(office_code(code.trim()))
+}
+
+/// The progressive number of a valid 11-digit code within its issuing office,
+/// i.e. its first 7 digits. See [temporary_office_code].
+pub fn temporary_sequence(code: &str) -> Result<u32, FiscalCodeError> {
+    classify_numeric(code)?;
+
+    Ok(code.trim()[..7].parse().expect("digits"))
+}
+
+fn office_code(code: &str) -> u16 {
+    code[7..10].parse().expect("digits")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_classify_numeric() {
+        assert_eq!(
+            classify_numeric("00743110157"),
+            Ok(NumericCodeKind::Ambiguous)
+        );
+        assert_eq!(
+            classify_numeric("00000000992"),
+            Ok(NumericCodeKind::Ambiguous)
+        );
+        assert_eq!(
+            classify_numeric(" 12345678903 "),
+            Ok(NumericCodeKind::Temporary)
+        );
+        assert_eq!(
+            classify_numeric("12345670009"),
+            Ok(NumericCodeKind::Temporary)
+        );
+
+        assert_eq!(
+            classify_numeric("12345678904"),
+            Err(FiscalCodeError::InvalidTemporaryCheckCharacter)
+        );
+        assert_eq!(
+            classify_numeric("1234567890"),
+            Err(FiscalCodeError::InvalidLength)
+        );
+        assert_eq!(
+            classify_numeric("12345A78903"),
+            Err(FiscalCodeError::InvalidCharacter {
+                position: 5,
+                expected: PositionKind::ExpectedDigit
+            })
+        );
+    }
+
+    #[test]
+    fn test_temporary_fields() {
+        assert_eq!(temporary_office_code(" 12345678903 "), Ok(890));
+        assert_eq!(temporary_sequence(" 12345678903 "), Ok(1234567));
+        assert_eq!(temporary_office_code("00743110157"), Ok(15));
+        assert_eq!(temporary_sequence("00743110157"), Ok(74311));
+
+        assert_eq!(
+            temporary_office_code("12345678904"),
+            Err(FiscalCodeError::InvalidTemporaryCheckCharacter)
+        );
+        assert_eq!(
+            temporary_sequence("1234567890"),
+            Err(FiscalCodeError::InvalidLength)
+        );
+    }
+}
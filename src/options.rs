@@ -0,0 +1,232 @@
+use chrono::{Datelike, NaiveDate};
+
+use crate::{is_temporary_code, parse, validate_or_error, FiscalCodeError};
+
+/// Additional checks for [validate_with_options], all disabled by default.
+///
+/// Build it with the fields needed and `..Default::default()`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ValidateOptions {
+    /// Reject codes of people born before this year.
+    pub min_birth_year: Option<i32>,
+    /// Reject codes of people born after this date, e.g. today: since codes are
+    /// derived from real birth dates, a later date means the wrong century was picked.
+    pub max_birth_date: Option<NaiveDate>,
+    /// Remove all the ASCII whitespace, not only the surrounding one, e.g. `GNTMTT99 C27H501F`.
+    ///
+    /// This is lenient, meant to normalize user input with spaces pasted in the middle.
+    pub strip_whitespace: bool,
+    /// Reject lowercase letters instead of uppercasing them, e.g. to catch upstream
+    /// normalization bugs. Surrounding whitespace is still trimmed, and the position in the
+    /// error counts any whitespace inside the code, even with [Self::strip_whitespace].
+    pub require_uppercase: bool,
+    /// Reject codes of people born outside these countries, given as in
+    /// [crate::PlaceOfBirth::country_code] and compared case-insensitively, e.g. `["IT", "FR"]`.
+    /// Italy has to be listed to be allowed.
+    pub allowed_countries: Option<Vec<String>>,
+}
+
+/// Same as [validate_or_error], with the additional checks of `options`.
+/// Temporary codes are supported, but they carry no data for the checks: if the birth date
+/// or the country is checked, a valid one is rejected with
+/// [FiscalCodeError::TemporaryCodeNotAllowed].
+pub fn validate_with_options(code: &str, options: &ValidateOptions) -> Result<(), FiscalCodeError> {
+    // before stripping, so that the position is the one of the input
+    if options.require_uppercase {
+        if let Some(position) = code.trim().chars().position(|c| c.is_lowercase()) {
+            return Err(FiscalCodeError::LowercaseCharacter { position });
+        }
+    }
+    let stripped: String;
+    let code = if options.strip_whitespace {
+        stripped = code.chars().filter(|c| !c.is_ascii_whitespace()).collect();
+        &stripped
+    } else {
+        code
+    };
+
+    if is_temporary_code(code.trim()) {
+        let checked = options.min_birth_year.is_some()
+            || options.max_birth_date.is_some()
+            || options.allowed_countries.is_some();
+        validate_or_error(code)?;
+        return if checked {
+            Err(FiscalCodeError::TemporaryCodeNotAllowed)
+        } else {
+            Ok(())
+        };
+    }
+
+    let code = parse(code)?;
+    let born_on = code.born_on();
+    let too_early = options
+        .min_birth_year
+        .is_some_and(|year| born_on.year() < year);
+    let too_late = options.max_birth_date.is_some_and(|date| born_on > date);
+    if too_early || too_late {
+        return Err(FiscalCodeError::ImplausibleBirthDate { born_on });
+    }
+    let country_code = &code.place_of_birth().country_code;
+    if let Some(allowed_countries) = &options.allowed_countries {
+        if !allowed_countries
+            .iter()
+            .any(|allowed| allowed.trim().eq_ignore_ascii_case(country_code))
+        {
+            return Err(FiscalCodeError::CountryNotAllowed {
+                country_code: country_code.clone(),
+            });
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_with_options() {
+        let date = |y, m, d| NaiveDate::from_ymd_opt(y, m, d).unwrap();
+
+        //spell-checker: disable
+        assert_eq!(
+            validate_with_options("GNTMTT99C27H501F", &ValidateOptions::default()),
+            Ok(())
+        );
+
+        let options = ValidateOptions {
+            min_birth_year: Some(2000),
+            ..Default::default()
+        };
+        assert_eq!(
+            validate_with_options("GNTMTT99C27H501F", &options),
+            Err(FiscalCodeError::ImplausibleBirthDate {
+                born_on: date(1999, 3, 27)
+            })
+        );
+        assert_eq!(
+            validate_with_options("12345678903", &options),
+            Err(FiscalCodeError::TemporaryCodeNotAllowed)
+        );
+        assert_eq!(
+            validate_with_options("12345678904", &options),
+            Err(FiscalCodeError::InvalidTemporaryCheckCharacter)
+        );
+        assert_eq!(
+            validate_with_options("12345678903", &ValidateOptions::default()),
+            Ok(())
+        );
+
+        let options = ValidateOptions {
+            max_birth_date: Some(date(1999, 3, 26)),
+            ..Default::default()
+        };
+        assert_eq!(
+            validate_with_options("GNTMTT99C27H501F", &options),
+            Err(FiscalCodeError::ImplausibleBirthDate {
+                born_on: date(1999, 3, 27)
+            })
+        );
+        let options = ValidateOptions {
+            min_birth_year: Some(1999),
+            max_birth_date: Some(date(1999, 3, 27)),
+            ..Default::default()
+        };
+        assert_eq!(validate_with_options("GNTMTT99C27H501F", &options), Ok(()));
+
+        assert_eq!(
+            validate_with_options("GNTMTT99C27H501K", &options),
+            Err(FiscalCodeError::InvalidCheckCharacter {
+                found: 'K',
+                expected: 'F'
+            })
+        );
+        //spell-checker: enable
+    }
+
+    #[test]
+    fn test_validate_with_options_strip_whitespace() {
+        //spell-checker: disable
+        let code = "GNT MTT 99C27\tH501 F";
+        assert_eq!(
+            validate_with_options(code, &ValidateOptions::default()),
+            Err(FiscalCodeError::InvalidLength)
+        );
+
+        let options = ValidateOptions {
+            strip_whitespace: true,
+            ..Default::default()
+        };
+        assert_eq!(validate_with_options(code, &options), Ok(()));
+        assert_eq!(validate_with_options("123 4567 8903", &options), Ok(()));
+        //spell-checker: enable
+    }
+
+    #[test]
+    fn test_validate_with_options_require_uppercase() {
+        //spell-checker: disable
+        let options = ValidateOptions {
+            require_uppercase: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            validate_with_options(" GNTMTT99C27H501F ", &options),
+            Ok(())
+        );
+        assert_eq!(
+            validate_with_options("GNTMTT99C27h501F", &options),
+            Err(FiscalCodeError::LowercaseCharacter { position: 11 })
+        );
+        assert_eq!(
+            validate_with_options("GNTMTT99C27h501F", &ValidateOptions::default()),
+            Ok(())
+        );
+
+        // the position is the one of the input, not of the stripped code
+        let options = ValidateOptions {
+            strip_whitespace: true,
+            ..options
+        };
+        assert_eq!(
+            validate_with_options(" GNT MTT99C27h501F", &options),
+            Err(FiscalCodeError::LowercaseCharacter { position: 12 })
+        );
+        //spell-checker: enable
+    }
+
+    #[test]
+    fn test_validate_with_options_allowed_countries() {
+        let options = ValidateOptions {
+            allowed_countries: Some(vec!["it".into(), "FR".into()]),
+            ..Default::default()
+        };
+        //spell-checker: disable
+        assert_eq!(validate_with_options("GNTMTT99C27H501F", &options), Ok(()));
+        assert_eq!(
+            validate_with_options("FCKTSS05C01Z130C", &options),
+            Err(FiscalCodeError::CountryNotAllowed {
+                country_code: "SM".into()
+            })
+        );
+        //spell-checker: enable
+        // a temporary code has no country to check
+        assert_eq!(
+            validate_with_options("12345678903", &options),
+            Err(FiscalCodeError::TemporaryCodeNotAllowed)
+        );
+
+        let options = ValidateOptions {
+            allowed_countries: Some(vec![]),
+            ..Default::default()
+        };
+        //spell-checker: disable
+        assert_eq!(
+            validate_with_options("GNTMTT99C27H501F", &options),
+            Err(FiscalCodeError::CountryNotAllowed {
+                country_code: "IT".into()
+            })
+        );
+        //spell-checker: enable
+    }
+}
@@ -0,0 +1,37 @@
+use rayon::prelude::*;
+
+use crate::{validate_or_error, FiscalCodeError};
+
+/// Same as [crate::validate_batch], validating the codes concurrently on the rayon
+/// thread pool. The results are in the same order as the codes.
+pub fn validate_batch_parallel(codes: &[String]) -> Vec<Result<(), FiscalCodeError>> {
+    codes
+        .par_iter()
+        .map(|code| validate_or_error(code))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::validate_batch;
+
+    #[test]
+    fn test_validate_batch_parallel() {
+        //spell-checker: disable
+        let codes: Vec<String> = [
+            "GNTMTT99C27H501F",
+            "GNTMTT99C27H501K",
+            "12345678903",
+            "TOOSHORT",
+        ]
+        .iter()
+        .cycle()
+        .take(100)
+        .map(|code| code.to_string())
+        .collect();
+        //spell-checker: enable
+        let serial: Vec<&str> = codes.iter().map(String::as_str).collect();
+        assert_eq!(validate_batch_parallel(&codes), validate_batch(&serial));
+    }
+}
@@ -0,0 +1,46 @@
+//! The regular expressions used across the crate, defined once.
+
+/// A code without omocodia alterations, with a group for each field:
+/// surname, name, year, month, day, town and check character.
+/// Codes are decoded by position, this only checks the canonical output in the tests.
+#[cfg(test)]
+pub(crate) const CANONICAL_CODE: &str =
+    r"^([A-Z]{3})([A-Z]{3})([0-9]{2})([A-Z])([0-9]{2})([A-Z][0-9]{3})([A-Z])$";
+
+/// A legacy code, without omocodia letters nor check character.
+pub(crate) const LEGACY_CODE: &str = r"^[A-Z]{6}[0-9]{2}[A-Z][0-9]{2}[A-Z][0-9]{3}$";
+
+/// An STP or ENI code: the prefix and 13 digits. `\d` would match any Unicode digit.
+pub(crate) const STP_ENI_CODE: &str = r"^(STP|ENI)[0-9]{13}$";
+
+/// A code inside a larger text, case-insensitive and allowing omocodia letters in place of
+/// the digits, with word boundaries instead of anchors.
+pub(crate) const EMBEDDED_CODE: &str =
+    r"(?i)\b[A-Z]{6}[0-9L-NP-V]{2}[A-Z][0-9L-NP-V]{2}[A-Z][0-9L-NP-V]{3}[A-Z]\b";
+
+/// A plausible surname or name block: consonants, then vowels, then `X` padding.
+pub(crate) const NAME_BLOCK: &str = r"^[B-DF-HJ-NP-TV-Z]*[AEIOU]*X*$";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use regex::Regex;
+
+    #[test]
+    fn test_patterns_compile() {
+        for pattern in [
+            CANONICAL_CODE,
+            LEGACY_CODE,
+            EMBEDDED_CODE,
+            NAME_BLOCK,
+            STP_ENI_CODE,
+        ] {
+            assert!(Regex::new(pattern).is_ok(), "invalid pattern {}", pattern);
+        }
+        assert_eq!(
+            Regex::new(CANONICAL_CODE).unwrap().captures_len(),
+            8,
+            "a group for each field"
+        );
+    }
+}
@@ -0,0 +1,158 @@
+use crate::{
+    calculate_check_character, calculate_check_character_temporary, normalize_homoglyphs, validate,
+};
+
+/// The characters commonly mistaken for one another when a code is read by OCR or
+/// typed from paper, in both directions.
+const OCR_CONFUSIONS: [(u8, u8); 7] = [
+    (b'0', b'O'),
+    (b'1', b'I'),
+    (b'1', b'L'),
+    (b'2', b'Z'),
+    (b'5', b'S'),
+    (b'6', b'G'),
+    (b'8', b'B'),
+];
+
+/// Suggest the valid codes an invalid one was likely meant to be, e.g. to offer a
+/// correction during data entry. Temporary codes are supported.
+///
+/// The candidates are the code with:
+/// - its check character recomputed;
+/// - two adjacent characters swapped, e.g. the digits of the year;
+/// - a character replaced by the one it's often mistaken for, e.g. `O` and `0`;
+///
+/// and the last two with the check character recomputed as well. Only the valid ones are
+/// returned, without duplicates, sorted by their edit distance from the code (counting a
+/// swap as a single edit) and then alphabetically.
+///
+/// The input is trimmed, uppercased and has its lookalike characters replaced as in
+/// [normalize_homoglyphs]. A valid code, or one of the wrong length, gives no candidates.
+/// Note that the century of the birth year is not part of the code, so it never changes
+/// the check character.
+pub fn repair(code: &str) -> Vec<String> {
+    let code = normalize_homoglyphs(code.trim()).to_ascii_uppercase();
+    let temporary = code.bytes().all(|byte| byte.is_ascii_digit());
+    let length = if temporary { 11 } else { 16 };
+    if code.len() != length || !code.bytes().all(|byte| byte.is_ascii_alphanumeric()) {
+        return Vec::new();
+    }
+    if validate(&code) {
+        return Vec::new();
+    }
+
+    let code = code.into_bytes();
+    let mut bases = vec![code.clone()];
+    for i in 0..length - 1 {
+        let mut swapped = code.clone();
+        swapped.swap(i, i + 1);
+        bases.push(swapped);
+    }
+    for i in 0..length {
+        for &(a, b) in &OCR_CONFUSIONS {
+            let replacement = match code[i] {
+                found if found == a => b,
+                found if found == b => a,
+                _ => continue,
+            };
+            let mut replaced = code.clone();
+            replaced[i] = replacement;
+            bases.push(replaced);
+        }
+    }
+
+    let mut candidates: Vec<(usize, String)> = bases
+        .into_iter()
+        .flat_map(|base| {
+            let mut recomputed = base.clone();
+            let check_character = if temporary {
+                // an OCR replacement may have put a letter in a temporary code
+                recomputed[..10]
+                    .iter()
+                    .all(u8::is_ascii_digit)
+                    .then(|| calculate_check_character_temporary(ascii(&recomputed[..10])))
+            } else {
+                Some(calculate_check_character(ascii(&recomputed)))
+            };
+            if let Some(check_character) = check_character {
+                recomputed[length - 1] = check_character as u8;
+            }
+            [base, recomputed]
+        })
+        .map(|candidate| String::from_utf8(candidate).expect("ASCII code"))
+        .filter(|candidate| validate(candidate))
+        .map(|candidate| (edit_distance(&code, candidate.as_bytes()), candidate))
+        .collect();
+    candidates.sort_unstable();
+    candidates.dedup();
+
+    candidates
+        .into_iter()
+        .map(|(_, candidate)| candidate)
+        .collect()
+}
+
+fn ascii(bytes: &[u8]) -> &str {
+    std::str::from_utf8(bytes).expect("ASCII code")
+}
+
+/// The optimal string alignment distance, where swapping two adjacent characters is a
+/// single edit.
+fn edit_distance(a: &[u8], b: &[u8]) -> usize {
+    let mut distances = vec![vec![0; b.len() + 1]; a.len() + 1];
+    for (i, row) in distances.iter_mut().enumerate() {
+        row[0] = i;
+    }
+    distances[0] = (0..=b.len()).collect();
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            let mut distance = (distances[i - 1][j] + 1)
+                .min(distances[i][j - 1] + 1)
+                .min(distances[i - 1][j - 1] + cost);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                distance = distance.min(distances[i - 2][j - 2] + 1);
+            }
+            distances[i][j] = distance;
+        }
+    }
+    distances[a.len()][b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_repair() {
+        //spell-checker: disable
+        assert_eq!(repair("GNTMTT99C27H501X")[0], "GNTMTT99C27H501F");
+        assert!(repair("gntmtt99c27h5O1f").contains(&"GNTMTT99C27H501F".to_string()));
+
+        // the digits of the year swapped
+        let candidates = repair("RSSMRA58T10H501O");
+        assert!(candidates.contains(&"RSSMRA85T10H501O".to_string()));
+        assert!(candidates
+            .iter()
+            .all(|candidate| edit_distance(b"RSSMRA58T10H501O", candidate.as_bytes()) <= 2));
+        assert!(candidates.windows(2).all(|pair| {
+            edit_distance(b"RSSMRA58T10H501O", pair[0].as_bytes())
+                <= edit_distance(b"RSSMRA58T10H501O", pair[1].as_bytes())
+        }));
+
+        assert!(repair("GNTMTT99C27H501F").is_empty());
+        assert!(repair("GNTMTT99C27H501").is_empty());
+        assert!(repair("GNTMTT99C27H501-").is_empty());
+        //spell-checker: enable
+
+        assert_eq!(repair("12345678904")[0], "12345678903");
+        assert!(repair("21345678903").contains(&"12345678903".to_string()));
+    }
+
+    #[test]
+    fn test_edit_distance() {
+        assert_eq!(edit_distance(b"ABCD", b"ABCD"), 0);
+        assert_eq!(edit_distance(b"ABCD", b"ABDC"), 1);
+        assert_eq!(edit_distance(b"ABCD", b"ABDE"), 2);
+    }
+}
@@ -0,0 +1,66 @@
+//! The validated code types are (de)serialized as plain strings:
+//! deserialization fails with the [crate::FiscalCodeError] message on invalid codes.
+
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::{FiscalCode, FiscalCodeStr};
+
+impl Serialize for FiscalCode {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.representation())
+    }
+}
+
+impl<'de> Deserialize<'de> for FiscalCode {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let code = String::deserialize(deserializer)?;
+        code.parse().map_err(de::Error::custom)
+    }
+}
+
+impl Serialize for FiscalCodeStr {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for FiscalCodeStr {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let code = String::deserialize(deserializer)?;
+        code.parse().map_err(de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fiscal_code_serde() {
+        //spell-checker: disable
+        let code: FiscalCode = serde_json::from_str(r#""gntmtt99c27hrlmd""#).unwrap();
+        assert_eq!(code.representation_canonical(), "GNTMTT99C27H501F");
+        assert_eq!(
+            serde_json::to_string(&code).unwrap(),
+            r#""GNTMTT99C27HRLMD""#
+        );
+
+        let error = serde_json::from_str::<FiscalCode>(r#""GNTMTT99C27H501K""#).unwrap_err();
+        assert!(error
+            .to_string()
+            .starts_with("Invalid check character: found K, expected F"));
+        //spell-checker: enable
+    }
+
+    #[test]
+    fn test_fiscal_code_str_serde() {
+        //spell-checker: disable
+        let code: FiscalCodeStr = serde_json::from_str(r#""GNTMTT99C27H501F""#).unwrap();
+        assert_eq!(
+            serde_json::to_string(&code).unwrap(),
+            r#""GNTMTT99C27H501F""#
+        );
+        assert!(serde_json::from_str::<FiscalCodeStr>(r#""TOOSHORT""#).is_err());
+        //spell-checker: enable
+    }
+}
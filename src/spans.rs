@@ -0,0 +1,84 @@
+use std::ops::Range;
+
+/// A field of a code, see [field_spans].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[non_exhaustive]
+pub enum FieldKind {
+    Surname,
+    Name,
+    Year,
+    Month,
+    Day,
+    Town,
+    CheckCharacter,
+}
+
+/// Where a field is in a code, as byte offsets: `start` is included and `end` is not.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FieldSpan {
+    pub start: usize,
+    pub end: usize,
+    pub kind: FieldKind,
+}
+
+/// The fields in order, with the positions of their characters.
+pub(crate) const FIELDS: [(Range<usize>, FieldKind); 7] = [
+    (0..3, FieldKind::Surname),
+    (3..6, FieldKind::Name),
+    (6..8, FieldKind::Year),
+    (8..9, FieldKind::Month),
+    (9..11, FieldKind::Day),
+    (11..15, FieldKind::Town),
+    (15..16, FieldKind::CheckCharacter),
+];
+
+/// Locate the fields of a code, e.g. to highlight them in a text widget.
+///
+/// The code is not validated: the spans follow the fixed layout of the 16 characters,
+/// skipping the leading whitespace, and are byte offsets into `code` even if it contains
+/// multi-byte characters. The fields past the end of a shorter code are empty spans at its end.
+pub fn field_spans(code: &str) -> [FieldSpan; 7] {
+    let leading = code.len() - code.trim_start().len();
+    // the byte offset of each character position, up to the end of the 16th character
+    let offsets: Vec<usize> = code[leading..]
+        .char_indices()
+        .map(|(i, _)| leading + i)
+        .chain([code.len()])
+        .take(17)
+        .collect();
+    let offset = |position: usize| offsets[position.min(offsets.len() - 1)];
+
+    FIELDS.map(|(positions, kind)| FieldSpan {
+        start: offset(positions.start),
+        end: offset(positions.end),
+        kind,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_field_spans() {
+        //spell-checker: disable
+        let code = "GNTMTT99C27H501F";
+        //spell-checker: enable
+        let spans = field_spans(code);
+        assert_eq!(
+            spans.map(|span| &code[span.start..span.end]),
+            ["GNT", "MTT", "99", "C", "27", "H501", "F"]
+        );
+        assert_eq!(spans[5].kind, FieldKind::Town);
+
+        let spans = field_spans("  GNTMTT99C27H501F  ");
+        assert_eq!((spans[0].start, spans[6].end), (2, 18));
+
+        let code = "GNTMTTÀ9C27";
+        let spans = field_spans(code);
+        assert_eq!(&code[spans[2].start..spans[2].end], "À9");
+        assert_eq!(&code[spans[4].start..spans[4].end], "27");
+        assert_eq!((spans[5].start, spans[5].end), (code.len(), code.len()));
+    }
+}
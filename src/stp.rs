@@ -0,0 +1,59 @@
+use regex::Regex;
+use std::sync::LazyLock;
+
+use crate::patterns;
+
+/// The codes of the regions (and autonomous provinces) used in STP and ENI codes.
+const REGION_CODES: [&str; 21] = [
+    "010", "020", "030", "041", "042", "050", "060", "070", "080", "090", "100", "110", "120",
+    "130", "140", "150", "160", "170", "180", "190", "200",
+];
+
+/// Check if the string provided is a valid STP or ENI code.
+///
+/// These are issued by the health service to foreigners temporarily present in Italy
+/// (Straniero Temporaneamente Presente) or to EU citizens without health insurance
+/// (Europeo Non Iscritto), and are not fiscal codes. They are 16 characters long:
+/// `STP` or `ENI`, the code of the region, the code of the local health unit and a
+/// progressive number of 7 digits, e.g. `STP1201010000001`. There is no check character.
+pub fn validate_stp_eni(code: &str) -> bool {
+    static REGEX: LazyLock<Regex> =
+        LazyLock::new(|| Regex::new(patterns::STP_ENI_CODE).expect("valid regex"));
+    let code = code.trim().to_ascii_uppercase();
+
+    REGEX.is_match(&code) && REGION_CODES.contains(&&code[3..6])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_stp_eni() {
+        assert!(validate_stp_eni("STP1201010000001"));
+        assert!(validate_stp_eni(" eni0301090001234 "));
+        assert!(validate_stp_eni("STP0411010000001"));
+
+        // unknown region
+        assert!(!validate_stp_eni("STP9991010000001"));
+        assert!(!validate_stp_eni("STP120101000000"));
+        assert!(!validate_stp_eni("STP12010100000A1"));
+        assert!(!validate_stp_eni("XYZ1201010000001"));
+        // full-width and Arabic-Indic digits
+        assert!(!validate_stp_eni("STP１２０１０１００００００１"));
+        assert!(!validate_stp_eni("STP١٢٠١٠١٠٠٠٠٠٠١"));
+        // the long s, whose Unicode uppercase is `S`
+        assert!(!validate_stp_eni("ſTP1201010000001"));
+        assert_eq!(
+            crate::classify("ſTP1201010000001"),
+            crate::FiscalCodeKind::Invalid
+        );
+        assert_eq!(
+            crate::classify("STP١٢٠١٠١٠٠٠٠٠٠١"),
+            crate::FiscalCodeKind::Invalid
+        );
+        //spell-checker: disable
+        assert!(!validate_stp_eni("GNTMTT99C27H501F"));
+        //spell-checker: enable
+    }
+}
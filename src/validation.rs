@@ -0,0 +1,33 @@
+use validator::ValidationError;
+
+use crate::validate_or_error;
+
+/// A custom validation function for the `validator` crate, e.g.
+/// `#[validate(custom(function = "tommaso_fiscal_code::is_fiscal_code"))]`.
+///
+/// It wraps [validate_or_error], so temporary codes are supported. The error code is
+/// `fiscal_code` and the message is the one of the [crate::FiscalCodeError].
+pub fn is_fiscal_code(code: &str) -> Result<(), ValidationError> {
+    validate_or_error(code)
+        .map_err(|error| ValidationError::new("fiscal_code").with_message(error.to_string().into()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_fiscal_code() {
+        //spell-checker: disable
+        assert!(is_fiscal_code("GNTMTT99C27H501F").is_ok());
+        assert!(is_fiscal_code("12345678903").is_ok());
+
+        let error = is_fiscal_code("GNTMTT99C27H501K").unwrap_err();
+        assert_eq!(error.code, "fiscal_code");
+        assert_eq!(
+            error.message.as_deref(),
+            Some("Invalid check character: found K, expected F")
+        );
+        //spell-checker: enable
+    }
+}
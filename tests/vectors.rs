@@ -0,0 +1,114 @@
+//! Known-good codes, checked against the decoded fields.
+//!
+//! To report a code that is decoded wrongly, add it to [VECTORS] with the expected fields.
+
+use chrono::NaiveDate;
+use tommaso_fiscal_code::{parse, Gender};
+
+struct Vector {
+    code: &'static str,
+    surname: &'static str,
+    born_on: (i32, u32, u32),
+    gender: Gender,
+    belfiore: &'static str,
+}
+
+//spell-checker: disable
+const VECTORS: &[Vector] = &[
+    Vector {
+        code: "GNTMTT99C27H501F",
+        surname: "GNT",
+        born_on: (1999, 3, 27),
+        gender: Gender::Male,
+        belfiore: "H501",
+    },
+    Vector {
+        code: "RSSMRA85T10H501O",
+        surname: "RSS",
+        born_on: (1985, 12, 10),
+        gender: Gender::Male,
+        belfiore: "H501",
+    },
+    Vector {
+        code: "BNCLRA90M45F205M",
+        surname: "BNC",
+        born_on: (1990, 8, 5),
+        gender: Gender::Female,
+        belfiore: "F205",
+    },
+    // omocodia
+    Vector {
+        code: "GNTMTT99C27HRLMD",
+        surname: "GNT",
+        born_on: (1999, 3, 27),
+        gender: Gender::Male,
+        belfiore: "H501",
+    },
+    Vector {
+        code: "BNCLRA90M45F20RH",
+        surname: "BNC",
+        born_on: (1990, 8, 5),
+        gender: Gender::Female,
+        belfiore: "F205",
+    },
+    Vector {
+        code: "BNCLRAVLMQRFNLRO",
+        surname: "BNC",
+        born_on: (1990, 8, 5),
+        gender: Gender::Female,
+        belfiore: "F205",
+    },
+    // born abroad
+    Vector {
+        code: "FCKTSS05C01Z130C",
+        surname: "FCK",
+        born_on: (2005, 3, 1),
+        gender: Gender::Male,
+        belfiore: "Z130",
+    },
+    Vector {
+        code: "RSSMRA80A41Z106S",
+        surname: "RSS",
+        born_on: (1980, 1, 1),
+        gender: Gender::Female,
+        belfiore: "Z106",
+    },
+    Vector {
+        code: "VRDGPP70A01Z404P",
+        surname: "VRD",
+        born_on: (1970, 1, 1),
+        gender: Gender::Male,
+        belfiore: "Z404",
+    },
+    Vector {
+        code: "VRDGPP70A01ZQLQU",
+        surname: "VRD",
+        born_on: (1970, 1, 1),
+        gender: Gender::Male,
+        belfiore: "Z404",
+    },
+];
+//spell-checker: enable
+
+#[test]
+fn test_vectors() {
+    for vector in VECTORS {
+        let code = parse(vector.code).unwrap_or_else(|e| panic!("{}: {}", vector.code, e));
+        let (year, month, day) = vector.born_on;
+
+        assert_eq!(code.surname(), vector.surname, "{}", vector.code);
+        assert_eq!(
+            code.born_on(),
+            NaiveDate::from_ymd_opt(year, month, day).unwrap(),
+            "{}",
+            vector.code
+        );
+        assert_eq!(code.gender(), &vector.gender, "{}", vector.code);
+        assert_eq!(
+            &code.representation_canonical()[11..15],
+            vector.belfiore,
+            "{}",
+            vector.code
+        );
+    }
+}